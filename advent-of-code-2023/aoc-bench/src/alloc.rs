@@ -0,0 +1,68 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    ops::Sub,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// System allocator that counts every allocation, for benchmark reports.
+///
+/// Install it with `#[global_allocator]`; until then [`stats`] returns `None`.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record(size: usize) {
+        INSTALLED.store(true, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+}
+
+impl Sub for AllocStats {
+    type Output = AllocStats;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        AllocStats {
+            allocations: self.allocations - rhs.allocations,
+            bytes: self.bytes - rhs.bytes,
+        }
+    }
+}
+
+/// Totals since the process started, if [`CountingAlloc`] is the global allocator.
+pub fn stats() -> Option<AllocStats> {
+    INSTALLED.load(Ordering::Relaxed).then(|| AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+    })
+}
@@ -0,0 +1,17 @@
+use aoc_core::Puzzle;
+use criterion::{Criterion, Throughput};
+
+/// Registers every stage of `puzzle` as one criterion group.
+pub fn bench(c: &mut Criterion, puzzle: &dyn Puzzle, inputs: [&str; 2]) {
+    let mut group = c.benchmark_group(format!("day_{:02}", puzzle.day()));
+    puzzle
+        .stages(inputs, &mut |stage, run| {
+            group.throughput(Throughput::Bytes(stage.input(inputs).len() as u64));
+            group.bench_function(stage.to_string(), |b| {
+                b.iter(|| run().expect("stage should succeed"))
+            });
+            Ok(())
+        })
+        .expect("input should parse");
+    group.finish();
+}
@@ -0,0 +1,32 @@
+use aoc_core::{Part, Puzzle, Stage};
+use divan::{counter::BytesCount, Bencher};
+
+/// Runs `stage` of `puzzle` under divan.
+pub fn bench(puzzle: &dyn Puzzle, inputs: [&str; 2], stage: Stage, bencher: Bencher) {
+    let mut bencher = Some(bencher);
+    puzzle
+        .stages(inputs, &mut |visited, run| {
+            if let Some(bencher) = bencher.take_if(|_| visited == stage) {
+                bencher
+                    .counter(BytesCount::of_str(stage.input(inputs)))
+                    .bench_local(|| run().expect("stage should succeed"));
+            }
+            Ok(())
+        })
+        .expect("input should parse");
+}
+
+/// Solves `part` of `puzzle` from scratch on `copies` copies of its input, to
+/// see how the day scales, with and without the `parallel` feature.
+pub fn scaling(
+    puzzle: &dyn Puzzle,
+    inputs: [&str; 2],
+    part: Part,
+    copies: usize,
+    bencher: Bencher,
+) {
+    let input = crate::repeat_input(inputs[part.index()], copies);
+    bencher
+        .counter(BytesCount::of_str(&input))
+        .bench_local(|| puzzle.solve(part, &input).expect("part should succeed"));
+}
@@ -0,0 +1,78 @@
+use std::time::{Duration, Instant};
+
+use aoc_core::{Puzzle, StageFn};
+
+use crate::{alloc, report::BenchRecord};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    pub samples: u32,
+    /// Fast stages run several iterations per sample so timer overhead stays small.
+    pub min_sample_time: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            samples: 100,
+            min_sample_time: Duration::from_micros(100),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub median: Duration,
+    pub min: Duration,
+    /// Per iteration, when allocations are being counted.
+    pub allocs: Option<alloc::AllocStats>,
+}
+
+pub fn measure(run: &mut StageFn<'_>, config: &Config) -> miette::Result<Measurement> {
+    let start = Instant::now();
+    run()?;
+    let once = start.elapsed().max(Duration::from_nanos(1));
+    let iters = (config.min_sample_time.as_nanos() / once.as_nanos()).clamp(1, 10_000) as u32;
+
+    let sample_count = config.samples.max(1);
+    let mut samples = Vec::with_capacity(sample_count as usize);
+    let before = alloc::stats();
+    for _ in 0..sample_count {
+        let start = Instant::now();
+        for _ in 0..iters {
+            run()?;
+        }
+        samples.push(start.elapsed() / iters);
+    }
+    let after = alloc::stats();
+
+    samples.sort_unstable();
+    let runs = u64::from(sample_count) * u64::from(iters);
+    Ok(Measurement {
+        median: samples[samples.len() / 2],
+        min: samples[0],
+        allocs: before.zip(after).map(|(before, after)| {
+            let total = after - before;
+            alloc::AllocStats {
+                allocations: total.allocations / runs,
+                bytes: total.bytes / runs,
+            }
+        }),
+    })
+}
+
+/// Measures every stage of `puzzle`, one record per stage.
+pub fn bench(
+    puzzle: &dyn Puzzle,
+    inputs: [&str; 2],
+    config: &Config,
+) -> miette::Result<Vec<BenchRecord>> {
+    let mut records = vec![];
+    puzzle.stages(inputs, &mut |stage, run| {
+        let measurement = measure(run, config)?;
+        let input_bytes = stage.input(inputs).len();
+        records.push(BenchRecord::new(puzzle, stage, input_bytes, measurement));
+        Ok(())
+    })?;
+    Ok(records)
+}
@@ -0,0 +1,253 @@
+use std::{fmt, io::ErrorKind, path::Path};
+
+use miette::{Context, IntoDiagnostic};
+use serde::{Deserialize, Serialize};
+
+use crate::report::BenchRecord;
+
+/// Benchmark results of one commit, stored as a line of the history file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub commit: String,
+    /// The working tree had uncommitted changes when this was measured.
+    pub dirty: bool,
+    pub timestamp: u64,
+    pub records: Vec<BenchRecord>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct History {
+    pub entries: Vec<Entry>,
+}
+
+impl History {
+    /// Reads a JSON lines history, a missing file being an empty history.
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(History::default()),
+            Err(error) => {
+                return Err(error)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("reading {}", path.display()))
+            }
+        };
+        let entries = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .into_diagnostic()
+            .wrap_err_with(|| format!("parsing {}", path.display()))?;
+        Ok(History { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<()> {
+        let mut content = String::new();
+        for entry in &self.entries {
+            content.push_str(&serde_json::to_string(entry).into_diagnostic()?);
+            content.push('\n');
+        }
+        std::fs::write(path, content)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("writing {}", path.display()))
+    }
+
+    /// Adds `entry`, replacing an earlier run of the same commit and tree state.
+    pub fn record(&mut self, entry: Entry) {
+        self.entries
+            .retain(|old| (old.commit.as_str(), old.dirty) != (entry.commit.as_str(), entry.dirty));
+        self.entries.push(entry);
+    }
+
+    pub fn latest(&self) -> Option<&Entry> {
+        self.entries.last()
+    }
+
+    /// The latest clean run of `commit`, falling back to a dirty one.
+    pub fn find(&self, commit: &str) -> Option<&Entry> {
+        let mut runs = self
+            .entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.commit == commit);
+        runs.clone()
+            .find(|entry| !entry.dirty)
+            .or_else(|| runs.next())
+    }
+
+    /// The latest run of any commit other than `commit`.
+    pub fn previous(&self, commit: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.commit != commit)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Median,
+    Allocations,
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Metric::Median => write!(f, "median ns"),
+            Metric::Allocations => write!(f, "allocations"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub stage: String,
+    pub metric: Metric,
+    pub baseline: u64,
+    pub current: u64,
+}
+
+impl Regression {
+    pub fn change_percent(&self) -> f64 {
+        if self.baseline == 0 {
+            f64::INFINITY
+        } else {
+            (self.current as f64 / self.baseline as f64 - 1.0) * 100.0
+        }
+    }
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02} {:<6} {:<12} {:>10} -> {:>10} ({:+.1}%)",
+            self.day,
+            self.stage,
+            self.metric.to_string(),
+            self.baseline,
+            self.current,
+            self.change_percent()
+        )
+    }
+}
+
+/// Median changes smaller than this are timer noise, whatever the percentage.
+const MEDIAN_NOISE_NS: u64 = 100;
+
+/// Stages of `current` whose median time or allocation count grew by more than
+/// `threshold_percent` over `baseline`. Stages missing from either side are skipped.
+pub fn compare(baseline: &Entry, current: &Entry, threshold_percent: f64) -> Vec<Regression> {
+    let limit = 1.0 + threshold_percent / 100.0;
+    let mut regressions = vec![];
+    for record in &current.records {
+        let Some(base) = baseline
+            .records
+            .iter()
+            .find(|base| (base.day, &base.stage) == (record.day, &record.stage))
+        else {
+            continue;
+        };
+        let metrics = [
+            (Metric::Median, Some(base.median_ns), Some(record.median_ns)),
+            (Metric::Allocations, base.allocations, record.allocations),
+        ];
+        for (metric, base, current) in metrics {
+            let (Some(base), Some(current)) = (base, current) else {
+                continue;
+            };
+            let noise = match metric {
+                Metric::Median => MEDIAN_NOISE_NS,
+                Metric::Allocations => 0,
+            };
+            if current as f64 > base as f64 * limit && current.saturating_sub(base) > noise {
+                regressions.push(Regression {
+                    day: record.day,
+                    stage: record.stage.clone(),
+                    metric,
+                    baseline: base,
+                    current,
+                });
+            }
+        }
+    }
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(commit: &str, dirty: bool, median_ns: u64, allocations: u64) -> Entry {
+        Entry {
+            commit: commit.to_string(),
+            dirty,
+            timestamp: 0,
+            records: vec![BenchRecord {
+                day: 3,
+                name: "Gear Ratios".to_string(),
+                stage: "part1".to_string(),
+                median_ns,
+                min_ns: median_ns,
+                allocations: Some(allocations),
+                alloc_bytes: None,
+                input_bytes: 10,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_compare_flags_regressions_beyond_threshold() {
+        let baseline = entry("a", false, 1_000, 10);
+        assert!(compare(&baseline, &entry("b", false, 1_090, 10), 10.0).is_empty());
+        assert!(compare(&entry("a", false, 1, 0), &entry("b", false, 50, 0), 10.0).is_empty());
+
+        let regressions = compare(&baseline, &entry("b", false, 1_200, 0), 10.0);
+        assert_eq!(1, regressions.len());
+        assert_eq!(Metric::Median, regressions[0].metric);
+
+        let regressions = compare(
+            &entry("a", false, 1_000, 0),
+            &entry("b", false, 1_000, 1),
+            10.0,
+        );
+        assert_eq!(Metric::Allocations, regressions[0].metric);
+        assert!(regressions[0].change_percent().is_infinite());
+    }
+
+    #[test]
+    fn test_compare_negative_threshold() {
+        let baseline = entry("a", false, 1_000, 10);
+        assert!(compare(&baseline, &entry("b", false, 950, 9), -10.0).is_empty());
+        let regressions = compare(&baseline, &entry("b", false, 1_200, 10), -50.0);
+        assert_eq!(1, regressions.len());
+        assert_eq!(Metric::Median, regressions[0].metric);
+    }
+
+    #[test]
+    fn test_record_replaces_same_commit() {
+        let mut history = History::default();
+        history.record(entry("a", false, 1, 0));
+        history.record(entry("a", true, 2, 0));
+        history.record(entry("b", false, 3, 0));
+        history.record(entry("a", false, 4, 0));
+        assert_eq!(3, history.entries.len());
+        assert_eq!(4, history.find("a").unwrap().records[0].median_ns);
+        assert_eq!("b", history.previous("a").unwrap().commit);
+    }
+
+    #[test]
+    fn test_save_and_load() -> miette::Result<()> {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.jsonl", std::process::id()));
+        let mut history = History::load(&path)?;
+        assert!(history.entries.is_empty());
+        history.record(entry("a", false, 1, 2));
+        history.save(&path)?;
+        let loaded = History::load(&path);
+        std::fs::remove_file(&path).into_diagnostic()?;
+        assert_eq!(history, loaded?);
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+pub mod alloc;
+pub mod harness;
+pub mod history;
+pub mod report;
+
+#[cfg(feature = "criterion")]
+pub mod criterion;
+#[cfg(feature = "divan")]
+pub mod divan;
+
+#[cfg(feature = "criterion")]
+#[doc(hidden)]
+pub use ::criterion as criterion_crate;
+#[cfg(feature = "divan")]
+#[doc(hidden)]
+pub use ::divan as divan_crate;
+
+#[cfg(all(feature = "divan", feature = "criterion"))]
+use aoc_core::Puzzle;
+#[doc(hidden)]
+pub use aoc_core::{Part, Stage};
+
+/// Input sizes, in copies of the day's input, of the `scaling` benches.
+pub const SCALES: [usize; 3] = [1, 8, 64];
+
+/// `copies` copies of `input`, one after the other, as a larger generated input.
+pub fn repeat_input(input: &str, copies: usize) -> String {
+    let mut input = input.trim_end().to_string();
+    input.push('\n');
+    input.repeat(copies)
+}
+
+/// Entry point of a day's bench binary, see [`benches!`].
+///
+/// The backend is picked with `AOC_BENCH`: `divan` (the default),
+/// `criterion`, or `json` to print [`report::BenchRecord`]s on stdout and
+/// a summary table on stderr.
+#[cfg(all(feature = "divan", feature = "criterion"))]
+pub fn main(puzzle: &dyn Puzzle, inputs: [&str; 2]) {
+    let backend = std::env::var("AOC_BENCH").unwrap_or_default();
+    let result = match backend.as_str() {
+        "" | "divan" => {
+            ::divan::main();
+            Ok(())
+        }
+        "criterion" => {
+            let mut c = ::criterion::Criterion::default().configure_from_args();
+            criterion::bench(&mut c, puzzle, inputs);
+            c.final_summary();
+            Ok(())
+        }
+        "json" => harness::bench(puzzle, inputs, &harness::Config::default()).map(|records| {
+            eprint!("{}", report::table(&records));
+            println!("{}", report::to_json(&records));
+        }),
+        other => Err(miette::miette!(
+            "unknown AOC_BENCH backend `{other}`, expected divan, criterion or json"
+        )),
+    };
+    if let Err(report) = result {
+        eprintln!("{report:?}");
+        std::process::exit(1);
+    }
+}
+
+/// Declares a day's benchmarks from its registered [`aoc_core::Solution`]:
+/// one divan bench per [`aoc_core::Stage`], plus the criterion and JSON
+/// backends, all reading the day's `input1.txt` and `input2.txt`.
+///
+/// divan reports allocation counts and sizes next to the timings.
+///
+/// The `scaling` benches solve each part on [`SCALES`] copies of the input;
+/// run them with and without the day's `parallel` feature to compare.
+///
+/// ```ignore
+/// aoc_bench::benches!(day_03::Day03);
+/// ```
+#[cfg(all(feature = "divan", feature = "criterion"))]
+#[macro_export]
+macro_rules! benches {
+    ($day:expr) => {
+        #[global_allocator]
+        static ALLOC: $crate::divan_crate::AllocProfiler<$crate::alloc::CountingAlloc> =
+            $crate::divan_crate::AllocProfiler::new($crate::alloc::CountingAlloc);
+
+        const INPUTS: [&str; 2] = [
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input1.txt")),
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input2.txt")),
+        ];
+
+        fn main() {
+            $crate::main(&$day, INPUTS);
+        }
+
+        #[$crate::divan_crate::bench(crate = $crate::divan_crate)]
+        fn parse(bencher: $crate::divan_crate::Bencher) {
+            $crate::divan::bench(&$day, INPUTS, $crate::Stage::Parse, bencher);
+        }
+
+        #[$crate::divan_crate::bench(crate = $crate::divan_crate)]
+        fn part1(bencher: $crate::divan_crate::Bencher) {
+            $crate::divan::bench(
+                &$day,
+                INPUTS,
+                $crate::Stage::Solve($crate::Part::One),
+                bencher,
+            );
+        }
+
+        #[$crate::divan_crate::bench(crate = $crate::divan_crate)]
+        fn part2(bencher: $crate::divan_crate::Bencher) {
+            $crate::divan::bench(
+                &$day,
+                INPUTS,
+                $crate::Stage::Solve($crate::Part::Two),
+                bencher,
+            );
+        }
+
+        mod scaling {
+            #[$crate::divan_crate::bench(crate = $crate::divan_crate, args = $crate::SCALES)]
+            fn part1(bencher: $crate::divan_crate::Bencher, copies: usize) {
+                $crate::divan::scaling(&$day, super::INPUTS, $crate::Part::One, copies, bencher);
+            }
+
+            #[$crate::divan_crate::bench(crate = $crate::divan_crate, args = $crate::SCALES)]
+            fn part2(bencher: $crate::divan_crate::Bencher, copies: usize) {
+                $crate::divan::scaling(&$day, super::INPUTS, $crate::Part::Two, copies, bencher);
+            }
+        }
+    };
+}
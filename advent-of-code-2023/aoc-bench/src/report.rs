@@ -0,0 +1,131 @@
+use std::{fmt::Write, time::Duration};
+
+use aoc_core::{Puzzle, Stage};
+use serde::{Deserialize, Serialize};
+
+use crate::harness::Measurement;
+
+/// One measured stage of one day, as exported to JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchRecord {
+    pub day: u8,
+    pub name: String,
+    pub stage: String,
+    pub median_ns: u64,
+    pub min_ns: u64,
+    /// Allocations per iteration, absent when they were not counted.
+    pub allocations: Option<u64>,
+    pub alloc_bytes: Option<u64>,
+    pub input_bytes: usize,
+}
+
+impl BenchRecord {
+    pub fn new(
+        puzzle: &dyn Puzzle,
+        stage: Stage,
+        input_bytes: usize,
+        measurement: Measurement,
+    ) -> Self {
+        BenchRecord {
+            day: puzzle.day(),
+            name: puzzle.name().to_string(),
+            stage: stage.to_string(),
+            median_ns: measurement.median.as_nanos() as u64,
+            min_ns: measurement.min.as_nanos() as u64,
+            allocations: measurement.allocs.map(|allocs| allocs.allocations),
+            alloc_bytes: measurement.allocs.map(|allocs| allocs.bytes),
+            input_bytes,
+        }
+    }
+}
+
+pub fn to_json(records: &[BenchRecord]) -> String {
+    serde_json::to_string_pretty(records).expect("records serialize to json")
+}
+
+fn nanos(ns: u64) -> String {
+    format!("{:.2?}", Duration::from_nanos(ns))
+}
+
+fn count(value: Option<u64>) -> String {
+    value.map_or("-".to_string(), |value| value.to_string())
+}
+
+/// Human readable summary of `records`, with a per-day total of the medians.
+pub fn table(records: &[BenchRecord]) -> String {
+    let mut table = format!(
+        "{:<4} {:<16} {:<6} {:>12} {:>12} {:>8} {:>10} {:>8}\n",
+        "day", "name", "stage", "median", "min", "allocs", "bytes", "input"
+    );
+    let mut days = records.iter().map(|record| record.day).collect::<Vec<_>>();
+    days.dedup();
+    for day in days {
+        let day_records = records.iter().filter(|record| record.day == day);
+        for record in day_records.clone() {
+            writeln!(
+                table,
+                "{:<4} {:<16} {:<6} {:>12} {:>12} {:>8} {:>10} {:>8}",
+                format!("{:02}", record.day),
+                record.name,
+                record.stage,
+                nanos(record.median_ns),
+                nanos(record.min_ns),
+                count(record.allocations),
+                count(record.alloc_bytes),
+                record.input_bytes,
+            )
+            .expect("writing to a String");
+        }
+        let total = day_records.map(|record| record.median_ns).sum::<u64>();
+        writeln!(
+            table,
+            "{:<4} {:<16} {:<6} {:>12}",
+            "",
+            "",
+            "total",
+            nanos(total)
+        )
+        .expect("writing to a String");
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u8, stage: &str, median_ns: u64) -> BenchRecord {
+        BenchRecord {
+            day,
+            name: format!("Day {day}"),
+            stage: stage.to_string(),
+            median_ns,
+            min_ns: median_ns / 2,
+            allocations: Some(3),
+            alloc_bytes: None,
+            input_bytes: 10,
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let records = vec![record(1, "parse", 1_000), record(1, "part1", 2_000)];
+        let parsed: Vec<BenchRecord> = serde_json::from_str(&to_json(&records)).unwrap();
+        assert_eq!(records, parsed);
+    }
+
+    #[test]
+    fn test_table_totals_each_day() {
+        let table = table(&[
+            record(1, "part1", 1_000),
+            record(1, "part2", 2_000),
+            record(2, "part1", 5_000),
+        ]);
+        let totals = table
+            .lines()
+            .filter(|line| line.contains("total"))
+            .map(|line| line.split_whitespace().last().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["3.00µs", "5.00µs"], totals);
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(feature = "dhat-heap")]
+use crate::{Part, Puzzle};
+
+/// Upper bounds on what a single `process` call, parsing included, may allocate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocBudget {
+    pub max_bytes: u64,
+    pub max_blocks: u64,
+}
+
+/// Solves every part of `puzzle` that declares a budget and fails if it
+/// allocates more than allowed.
+///
+/// Needs `dhat::Alloc` as the global allocator and a testing profiler, so call
+/// it from a dedicated integration test with `harness = false`:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: dhat::Alloc = dhat::Alloc;
+///
+/// fn main() {
+///     let _profiler = dhat::Profiler::builder().testing().build();
+///     aoc_core::budget::assert_within_budget(&day_01::Day01, INPUTS);
+/// }
+/// ```
+#[cfg(feature = "dhat-heap")]
+pub fn assert_within_budget(puzzle: &dyn Puzzle, inputs: [&str; 2]) {
+    for part in Part::ALL {
+        let Some(budget) = puzzle.budget(part) else {
+            continue;
+        };
+        let before = dhat::HeapStats::get();
+        puzzle
+            .solve(part, inputs[part.index()])
+            .expect("input should solve");
+        let after = dhat::HeapStats::get();
+
+        let bytes = after.total_bytes - before.total_bytes;
+        let blocks = after.total_blocks - before.total_blocks;
+        dhat::assert!(
+            bytes <= budget.max_bytes,
+            "day {:02} {part} allocated {bytes} bytes, budget is {}",
+            puzzle.day(),
+            budget.max_bytes
+        );
+        dhat::assert!(
+            blocks <= budget.max_blocks,
+            "day {:02} {part} allocated {blocks} blocks, budget is {}",
+            puzzle.day(),
+            budget.max_blocks
+        );
+    }
+}
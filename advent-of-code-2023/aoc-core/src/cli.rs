@@ -0,0 +1,166 @@
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use clap::Parser;
+
+use crate::{
+    report::{Format, Report},
+    ExplainFormat, InputStats, Part,
+};
+
+/// Flags shared by every day's `part1` and `part2` binaries.
+#[derive(Parser, Debug, Default)]
+pub struct Args {
+    /// Log more: `-v` for debug events, `-vv` for trace events. `RUST_LOG`
+    /// takes precedence when set
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Write the spans as Chrome trace-event JSON, viewable in
+    /// `chrome://tracing` or <https://ui.perfetto.dev>
+    #[arg(long, value_name = "PATH")]
+    pub chrome_trace: Option<PathBuf>,
+
+    /// Read the puzzle input from this file, or `-` for stdin, instead of
+    /// the bundled one. With `--no-validate` it is streamed in bounded memory
+    #[arg(long, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+
+    /// Solve the input without first checking its shape, which lets
+    /// `--input` stream it rather than read it whole
+    #[arg(long)]
+    pub no_validate: bool,
+
+    /// Print what each item of the input adds to the answer instead of the
+    /// answer alone, as a table or as CSV
+    #[arg(long, value_name = "FORMAT", num_args = 0..=1, default_missing_value = "table")]
+    pub explain: Option<ExplainFormat>,
+
+    /// How to print the answer: alone, or as JSON or CSV along with its
+    /// timing, input hash and validation stats
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+/// Builds the [`Report`] of a part binary and prints it as `--format` asks.
+pub struct Reporter {
+    format: Format,
+    report: Report,
+}
+
+impl Reporter {
+    pub fn new(args: &Args, day: u8, part: Part, name: &'static str) -> Self {
+        Reporter {
+            format: args.format,
+            report: Report::new(day, part, name),
+        }
+    }
+
+    /// Calls `solve`, timing it.
+    pub fn time<T>(&mut self, solve: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let solved = solve();
+        self.report.timed_total(start.elapsed());
+        solved
+    }
+
+    pub fn hashed(&mut self, sha256: String) {
+        self.report.input_sha256 = sha256;
+    }
+
+    pub fn validated(&mut self, stats: InputStats) {
+        self.report.validation = Some(stats);
+    }
+
+    pub fn print(mut self, answer: String) {
+        self.report.answer = answer;
+        match self.format {
+            Format::Text => println!("{}", self.report.answer),
+            Format::Json => println!("{}", self.report.json()),
+            Format::Csv => println!("{}\n{}", Report::CSV_HEADER, self.report.csv()),
+        }
+    }
+}
+
+/// Opens `path` for line by line reading, `-` being stdin.
+pub fn open(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    Ok(Box::new(BufReader::new(File::open(path)?)))
+}
+
+/// The whole of the `--input` file, or `bundled` without one.
+pub fn read_input(args: &Args, bundled: &'static str) -> io::Result<Cow<'static, str>> {
+    match &args.input {
+        Some(path) => Ok(Cow::Owned(io::read_to_string(open(path)?)?)),
+        None => Ok(Cow::Borrowed(bundled)),
+    }
+}
+
+/// Parses the binary's command line, exiting with usage on bad flags.
+pub fn args() -> Args {
+    Args::parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbose_count() {
+        assert_eq!(0, Args::parse_from(["part1"]).verbose);
+        assert_eq!(2, Args::parse_from(["part1", "-vv"]).verbose);
+    }
+
+    #[test]
+    fn test_chrome_trace() {
+        let args = Args::parse_from(["part1", "--chrome-trace", "trace.json"]);
+        assert_eq!(Some(PathBuf::from("trace.json")), args.chrome_trace);
+    }
+
+    #[test]
+    fn test_no_validate() {
+        assert!(!Args::parse_from(["part1"]).no_validate);
+        assert!(Args::parse_from(["part1", "--no-validate"]).no_validate);
+    }
+
+    #[test]
+    fn test_read_input_bundled() -> io::Result<()> {
+        let input = read_input(&Args::default(), "bundled")?;
+        assert!(matches!(input, Cow::Borrowed("bundled")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(None, Args::parse_from(["part1"]).explain);
+        assert_eq!(
+            Some(ExplainFormat::Table),
+            Args::parse_from(["part1", "--explain"]).explain
+        );
+        assert_eq!(
+            Some(ExplainFormat::Csv),
+            Args::parse_from(["part1", "--explain", "csv"]).explain
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(Format::Text, Args::parse_from(["part1"]).format);
+        assert_eq!(
+            Format::Json,
+            Args::parse_from(["part1", "--format", "json"]).format
+        );
+    }
+
+    #[test]
+    fn test_open_missing_input() {
+        assert!(open(Path::new("does/not/exist.txt")).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! How an answer is made up: one row per item of the input with what it adds
+//! to the answer, filled in by each day alongside its `process`.
+
+use std::fmt::Write;
+
+/// How an [`Explanation`] is printed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum ExplainFormat {
+    /// Aligned columns followed by the answer
+    #[default]
+    Table,
+    /// Comma separated values with a header row
+    Csv,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub columns: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+    /// The sum of the contributions, the same as the answer of the part.
+    pub answer: String,
+}
+
+impl Explanation {
+    pub fn new(columns: impl IntoIterator<Item = &'static str>) -> Self {
+        Explanation {
+            columns: columns.into_iter().collect(),
+            ..Explanation::default()
+        }
+    }
+
+    pub fn row(&mut self, cells: impl IntoIterator<Item = String>) {
+        self.rows.push(cells.into_iter().collect());
+    }
+
+    pub fn render(&self, format: ExplainFormat) -> String {
+        match format {
+            ExplainFormat::Table => self.table(),
+            ExplainFormat::Csv => self.csv(),
+        }
+    }
+
+    fn table(&self) -> String {
+        let mut widths = self
+            .columns
+            .iter()
+            .map(|column| column.len())
+            .collect::<Vec<_>>();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let mut output = String::new();
+        let mut line = |cells: &mut dyn Iterator<Item = &str>| {
+            let cells = cells
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:>width$}"))
+                .collect::<Vec<_>>();
+            output.push_str(cells.join("  ").trim_end());
+            output.push('\n');
+        };
+        line(&mut self.columns.iter().copied());
+        let rules = widths
+            .iter()
+            .map(|&width| "-".repeat(width))
+            .collect::<Vec<_>>();
+        line(&mut rules.iter().map(String::as_str));
+        for row in &self.rows {
+            line(&mut row.iter().map(String::as_str));
+        }
+        let _ = writeln!(output, "answer: {}", self.answer);
+        output
+    }
+
+    fn csv(&self) -> String {
+        let mut output = String::new();
+        let mut line = |cells: &mut dyn Iterator<Item = &str>| {
+            let cells = cells.map(csv_cell).collect::<Vec<_>>();
+            output.push_str(&cells.join(","));
+            output.push('\n');
+        };
+        line(&mut self.columns.iter().copied());
+        for row in &self.rows {
+            line(&mut row.iter().map(String::as_str));
+        }
+        output
+    }
+}
+
+/// `cell` as a CSV field, quoted when it has to be.
+pub fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explanation() -> Explanation {
+        let mut explanation = Explanation::new(["game", "verdict", "adds"]);
+        explanation.row(["1".to_string(), "possible".to_string(), "1".to_string()]);
+        explanation.row([
+            "3".to_string(),
+            "20 red, \"too many\"".to_string(),
+            "0".to_string(),
+        ]);
+        explanation.answer = "1".to_string();
+        explanation
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(
+            "game             verdict  adds
+----  ------------------  ----
+   1            possible     1
+   3  20 red, \"too many\"     0
+answer: 1
+",
+            explanation().render(ExplainFormat::Table)
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            "game,verdict,adds\n1,possible,1\n3,\"20 red, \"\"too many\"\"\",0\n",
+            explanation().render(ExplainFormat::Csv)
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! One form for every puzzle input, so that a file saved by a Windows editor
+//! solves the same as the one downloaded. The rules of [`normalize`] are:
+//!
+//! 1. a leading byte order mark is removed,
+//! 2. `\r\n` line endings become `\n`,
+//! 3. spaces and tabs at the end of a line are removed,
+//! 4. blank lines at the end are removed, and the last line ends with `\n`.
+//!
+//! Leading whitespace and blank lines between lines are kept, as they can be
+//! part of the puzzle.
+
+use std::borrow::Cow;
+
+const BOM: char = '\u{feff}';
+
+/// Applies the rules above, borrowing `input` when it already follows them.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = strip_bom(input);
+    if is_normal(input) {
+        return Cow::Borrowed(input);
+    }
+    let mut output = String::with_capacity(input.len());
+    for line in input.lines() {
+        output.push_str(trim_line(line));
+        output.push('\n');
+    }
+    output.truncate(output.trim_end_matches('\n').len());
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    Cow::Owned(output)
+}
+
+/// `input` without its byte order mark, if any.
+pub fn strip_bom(input: &str) -> &str {
+    input.strip_prefix(BOM).unwrap_or(input)
+}
+
+/// `line`, already split from its `\n`, without its `\r` and trailing blanks.
+pub fn trim_line(line: &str) -> &str {
+    line.strip_suffix('\r')
+        .unwrap_or(line)
+        .trim_end_matches([' ', '\t'])
+}
+
+/// A puzzle input written inline in a test, without the indentation of the
+/// code around it. Text on the line of the opening quote is kept as it is and
+/// the indentation shared by the lines below it is removed, so that rows keep
+/// leading spaces of their own. A line break right after the opening quote is
+/// skipped, and blank lines before the closing quote are dropped.
+pub fn dedent(text: &str) -> String {
+    let (first, rest) = match text.strip_prefix('\n') {
+        Some(text) => (None, text),
+        None => match text.split_once('\n') {
+            Some((first, rest)) => (Some(first), rest),
+            None => (Some(text), ""),
+        },
+    };
+    let is_blank = |line: &str| line.trim().is_empty();
+    let indent = rest
+        .lines()
+        .filter(|line| !is_blank(line))
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or_default();
+    let lines = first.into_iter().chain(rest.lines().map(|line| {
+        if is_blank(line) {
+            ""
+        } else {
+            &line[indent..]
+        }
+    }));
+    let mut output = String::with_capacity(text.len());
+    for line in lines {
+        output.push_str(line);
+        output.push('\n');
+    }
+    output.truncate(output.trim_end_matches('\n').len());
+    output.push('\n');
+    output
+}
+
+fn is_normal(input: &str) -> bool {
+    input.is_empty()
+        || (input.ends_with('\n')
+            && !input.ends_with("\n\n")
+            && !input.contains('\r')
+            && input
+                .lines()
+                .all(|line| trim_line(line).len() == line.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let normal = "Game 1: 3 blue\n\n  Game 3: 2 red\n";
+        assert!(matches!(normalize(normal), Cow::Borrowed(input) if input == normal));
+        for input in [
+            "\u{feff}Game 1: 3 blue\n\n  Game 3: 2 red\n",
+            "Game 1: 3 blue\r\n\r\n  Game 3: 2 red\r\n",
+            "Game 1: 3 blue \t\n \n  Game 3: 2 red  \n",
+            "Game 1: 3 blue\n\n  Game 3: 2 red",
+            "Game 1: 3 blue\n\n  Game 3: 2 red\n\n \r\n",
+        ] {
+            assert_eq!(normal, normalize(input), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_dedent() {
+        assert_eq!(
+            "467..114\n  .*....\n\n..35....\n",
+            dedent(
+                "467..114
+                  .*....
+
+                ..35....
+                "
+            )
+        );
+        assert_eq!(
+            " ..1\n.*..\n",
+            dedent(
+                "
+                 ..1
+                .*.."
+            )
+        );
+    }
+
+    #[test]
+    fn test_normalize_blank() {
+        assert_eq!("", normalize(""));
+        assert_eq!("", normalize("\u{feff}\r\n  \n"));
+    }
+}
@@ -0,0 +1,22 @@
+pub mod budget;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod explain;
+pub mod input;
+pub mod part;
+pub mod reader;
+#[cfg(feature = "report")]
+pub mod report;
+pub mod solution;
+pub mod stage;
+#[cfg(feature = "cli")]
+pub mod telemetry;
+pub mod validate;
+
+pub use budget::AllocBudget;
+pub use explain::{ExplainFormat, Explanation};
+pub use input::normalize;
+pub use part::Part;
+pub use solution::{Puzzle, Run, Solution, StageFn};
+pub use stage::Stage;
+pub use validate::InputStats;
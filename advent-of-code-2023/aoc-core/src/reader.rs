@@ -0,0 +1,73 @@
+use std::io::{self, BufRead};
+
+use crate::input::{strip_bom, trim_line};
+
+/// Calls `f` with the 1-based number and content of each line of `reader`,
+/// normalised as [`crate::normalize`] does. One buffer is reused, so memory
+/// stays bounded by the longest line whatever the size of the input.
+pub fn for_each_line<E: From<io::Error>>(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(usize, &str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut line = String::new();
+    let mut number = 0;
+    // Blank lines are held back until a line follows them, dropping those at
+    // the end.
+    let mut blanks = 0;
+    while reader.read_line(&mut line)? != 0 {
+        number += 1;
+        let mut content = trim_line(line.strip_suffix('\n').unwrap_or(&line));
+        if number == 1 {
+            content = strip_bom(content);
+        }
+        if content.is_empty() {
+            blanks += 1;
+        } else {
+            for blank in number - blanks..number {
+                f(blank, "")?;
+            }
+            blanks = 0;
+            f(number, content)?;
+        }
+        line.clear();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_each_line() -> io::Result<()> {
+        let mut lines = Vec::new();
+        for_each_line("one\r\ntwo\n\nfour".as_bytes(), |number, line| {
+            lines.push((number, line.to_string()));
+            Ok::<_, io::Error>(())
+        })?;
+        assert_eq!(
+            vec![
+                (1, "one".to_string()),
+                (2, "two".to_string()),
+                (3, String::new()),
+                (4, "four".to_string())
+            ],
+            lines
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_for_each_line_normalises() -> io::Result<()> {
+        let mut lines = Vec::new();
+        for_each_line(
+            "\u{feff}one \r\n\r\n  two\t\r\n \r\n\r\n".as_bytes(),
+            |_, line| {
+                lines.push(line.to_string());
+                Ok::<_, io::Error>(())
+            },
+        )?;
+        assert_eq!(vec!["one", "", "  two"], lines);
+        Ok(())
+    }
+}
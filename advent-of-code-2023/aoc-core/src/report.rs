@@ -0,0 +1,218 @@
+//! What a run prints once a part is solved, for people or for dashboards:
+//! the answer alone, one JSON object per line, or CSV rows.
+
+use std::{
+    io::{self, BufRead, Read},
+    time::Duration,
+};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{explain::csv_cell, InputStats, Part};
+
+/// How answers are printed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Format {
+    /// For people to read
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Comma separated values, after a header row
+    Csv,
+}
+
+/// One solved part.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub answer: String,
+    /// Unknown when parsing and solving are not timed apart.
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+    pub total_ns: u64,
+    /// Whether the answer was taken from the cache of an earlier run, in
+    /// which case it was not timed.
+    pub cached: bool,
+    /// Hex SHA-256 of the input as it was given, before normalisation.
+    pub input_sha256: String,
+    /// Missing when the input was not validated.
+    pub validation: Option<InputStats>,
+}
+
+impl Report {
+    pub const CSV_HEADER: &'static str = "day,part,name,answer,parse_ns,solve_ns,total_ns,\
+        cached,input_sha256,lines,bytes,width,line_ending,alphabet";
+
+    pub fn new(day: u8, part: Part, name: &'static str) -> Self {
+        Report {
+            day,
+            part: part.number(),
+            name,
+            ..Report::default()
+        }
+    }
+
+    /// Sets `parse_ns` and `solve_ns`, and `total_ns` as their sum.
+    pub fn timed(&mut self, parse: Duration, solve: Duration) {
+        self.parse_ns = Some(nanos(parse));
+        self.solve_ns = Some(nanos(solve));
+        self.total_ns = nanos(parse + solve);
+    }
+
+    /// Sets `total_ns` alone, for runs that parse and solve in one go.
+    pub fn timed_total(&mut self, total: Duration) {
+        self.total_ns = nanos(total);
+    }
+
+    pub fn json(&self) -> String {
+        serde_json::to_string(self).expect("reports serialize to json")
+    }
+
+    /// The CSV row of the report, to follow [`Report::CSV_HEADER`].
+    pub fn csv(&self) -> String {
+        let optional = |value: Option<u64>| value.map_or(String::new(), |value| value.to_string());
+        let mut cells = vec![
+            self.day.to_string(),
+            self.part.to_string(),
+            csv_cell(self.name),
+            csv_cell(&self.answer),
+            optional(self.parse_ns),
+            optional(self.solve_ns),
+            self.total_ns.to_string(),
+            self.cached.to_string(),
+            self.input_sha256.clone(),
+        ];
+        match &self.validation {
+            Some(stats) => cells.extend([
+                stats.lines.to_string(),
+                stats.bytes.to_string(),
+                stats.width.to_string(),
+                stats.line_ending.to_string(),
+                stats.alphabet.to_string(),
+            ]),
+            None => cells.resize(cells.len() + 5, String::new()),
+        }
+        cells.join(",")
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Hex SHA-256 of `input`.
+pub fn sha256(input: &str) -> String {
+    hex(&Sha256::digest(input.as_bytes()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Hashes what is read through it, for inputs that are streamed instead of
+/// held whole.
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl<R: BufRead> HashingReader<R> {
+    pub fn new(inner: R) -> Self {
+        HashingReader {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Hex SHA-256 of what was read so far.
+    pub fn sha256(&self) -> String {
+        hex(&self.hasher.clone().finalize())
+    }
+}
+
+impl<R: BufRead> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for HashingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The buffer is already filled, this does not read again.
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.hasher.update(&buf[..amt.min(buf.len())]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reader::for_each_line;
+
+    use super::*;
+
+    const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    fn report() -> Report {
+        let mut report = Report::new(2, Part::One, "Cube Conundrum");
+        report.answer = "8".to_string();
+        report.timed(Duration::from_nanos(1500), Duration::from_nanos(500));
+        report.input_sha256 = sha256("abc");
+        report
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            format!(
+                r#"{{"day":2,"part":1,"name":"Cube Conundrum","answer":"8","parse_ns":1500,"solve_ns":500,"total_ns":2000,"cached":false,"input_sha256":"{ABC}","validation":null}}"#
+            ),
+            report().json()
+        );
+        let mut validated = report();
+        validated.validation = Some(InputStats {
+            bytes: 3,
+            lines: 1,
+            width: 3,
+            ..InputStats::default()
+        });
+        assert!(validated.json().ends_with(
+            r#""validation":{"bytes":3,"lines":1,"width":3,"line_ending":"LF","alphabet":0}}"#
+        ));
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(14, Report::CSV_HEADER.split(',').count());
+        assert_eq!(
+            format!("2,1,Cube Conundrum,8,1500,500,2000,false,{ABC},,,,,"),
+            report().csv()
+        );
+    }
+
+    #[test]
+    fn test_hashing_reader() -> io::Result<()> {
+        let mut reader = HashingReader::new("ab\nc".as_bytes());
+        let mut lines = 0;
+        for_each_line(&mut reader, |_, _| {
+            lines += 1;
+            Ok::<_, io::Error>(())
+        })?;
+        assert_eq!(2, lines);
+        assert_eq!(sha256("ab\nc"), reader.sha256());
+        assert_eq!(ABC, sha256("abc"));
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use miette::Diagnostic;
+
+use crate::{normalize, AllocBudget, InputStats, Part, Stage};
+
+/// A day's puzzle: how to parse its input and how to solve both parts.
+///
+/// `Parsed` is whatever state both parts can share; days that have nothing
+/// to share can simply use `&'a str`.
+pub trait Solution {
+    const DAY: u8;
+    const NAME: &'static str;
+
+    type Parsed<'a>;
+    type Error: Diagnostic + Send + Sync + 'static;
+
+    /// Checks the shape of the input of `part` before it is parsed, see
+    /// [`crate::validate`].
+    fn validate(part: Part, input: &str) -> Result<InputStats, Self::Error>;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error>;
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<String, Self::Error>;
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<String, Self::Error>;
+
+    /// Allocation budget of `part`, checked by the `dhat-heap` tests.
+    fn budget(_part: Part) -> Option<AllocBudget> {
+        None
+    }
+
+    fn solve(parsed: &Self::Parsed<'_>, part: Part) -> Result<String, Self::Error> {
+        match part {
+            Part::One => Self::part1(parsed),
+            Part::Two => Self::part2(parsed),
+        }
+    }
+}
+
+/// The answer to one part, with parse and solve time reported separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub part: Part,
+    pub answer: String,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// One benchmarkable step, ready to be called repeatedly.
+pub type StageFn<'a> = dyn FnMut() -> miette::Result<()> + 'a;
+
+/// Object safe view of a [`Solution`] so days can be registered in a list
+/// and driven generically by runners, benches and tests. Inputs are
+/// normalised before they are parsed, see [`crate::input`].
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+    fn name(&self) -> &'static str;
+    fn budget(&self, part: Part) -> Option<AllocBudget>;
+    fn validate(&self, part: Part, input: &str) -> miette::Result<InputStats>;
+    fn solve(&self, part: Part, input: &str) -> miette::Result<String>;
+    fn solve_both(&self, input: &str) -> miette::Result<[String; 2]>;
+    fn run(&self, part: Part, input: &str) -> miette::Result<Run>;
+    /// Runs both parts from a single parse of `input`.
+    fn run_both(&self, input: &str) -> miette::Result<[Run; 2]>;
+    /// Hands `visit` each [`Stage`] of the day so a benchmark harness can time
+    /// parsing and solving separately. Parts are solved from input already
+    /// parsed outside of the measured closure.
+    fn stages(
+        &self,
+        inputs: [&str; 2],
+        visit: &mut dyn FnMut(Stage, &mut StageFn<'_>) -> miette::Result<()>,
+    ) -> miette::Result<()>;
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn name(&self) -> &'static str {
+        S::NAME
+    }
+
+    fn budget(&self, part: Part) -> Option<AllocBudget> {
+        S::budget(part)
+    }
+
+    fn validate(&self, part: Part, input: &str) -> miette::Result<InputStats> {
+        Ok(S::validate(part, input)?)
+    }
+
+    fn solve(&self, part: Part, input: &str) -> miette::Result<String> {
+        let input = normalize(input);
+        let parsed = S::parse(&input)?;
+        Ok(S::solve(&parsed, part)?)
+    }
+
+    fn solve_both(&self, input: &str) -> miette::Result<[String; 2]> {
+        let input = normalize(input);
+        let parsed = S::parse(&input)?;
+        Ok([S::part1(&parsed)?, S::part2(&parsed)?])
+    }
+
+    fn run(&self, part: Part, input: &str) -> miette::Result<Run> {
+        let input = normalize(input);
+        let start = Instant::now();
+        let parsed = S::parse(&input)?;
+        let parse = start.elapsed();
+        run_parsed::<S>(&parsed, part, parse)
+    }
+
+    fn run_both(&self, input: &str) -> miette::Result<[Run; 2]> {
+        let input = normalize(input);
+        let start = Instant::now();
+        let parsed = S::parse(&input)?;
+        let parse = start.elapsed();
+        Ok([
+            run_parsed::<S>(&parsed, Part::One, parse)?,
+            run_parsed::<S>(&parsed, Part::Two, parse)?,
+        ])
+    }
+
+    fn stages(
+        &self,
+        inputs: [&str; 2],
+        visit: &mut dyn FnMut(Stage, &mut StageFn<'_>) -> miette::Result<()>,
+    ) -> miette::Result<()> {
+        let inputs = inputs.map(normalize);
+        visit(Stage::Parse, &mut || {
+            black_box(S::parse(black_box(&inputs[0]))?);
+            Ok(())
+        })?;
+        for part in Part::ALL {
+            let parsed = S::parse(&inputs[part.index()])?;
+            visit(Stage::Solve(part), &mut || {
+                black_box(S::solve(black_box(&parsed), part)?);
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn run_parsed<S: Solution>(
+    parsed: &S::Parsed<'_>,
+    part: Part,
+    parse: Duration,
+) -> miette::Result<Run> {
+    let start = Instant::now();
+    let answer = S::solve(parsed, part)?;
+    Ok(Run {
+        part,
+        answer,
+        parse,
+        solve: start.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use thiserror::Error;
+
+    use super::*;
+
+    #[derive(Error, Diagnostic, Debug)]
+    enum LinesError {
+        #[error(transparent)]
+        InvalidInput(#[from] crate::validate::InvalidInput),
+    }
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: u8 = 42;
+        const NAME: &'static str = "Line Counter";
+
+        type Parsed<'a> = Vec<&'a str>;
+        type Error = LinesError;
+
+        fn validate(_part: Part, input: &str) -> Result<InputStats, LinesError> {
+            Ok(crate::validate::stats(input)?)
+        }
+        fn parse(input: &str) -> Result<Vec<&str>, LinesError> {
+            Ok(input.lines().collect())
+        }
+        fn part1(parsed: &Vec<&str>) -> Result<String, LinesError> {
+            Ok(parsed.len().to_string())
+        }
+        fn part2(parsed: &Vec<&str>) -> Result<String, LinesError> {
+            Ok(parsed
+                .iter()
+                .map(|line| line.len())
+                .sum::<usize>()
+                .to_string())
+        }
+    }
+
+    #[test]
+    fn test_puzzle_from_solution() -> miette::Result<()> {
+        let puzzle: &dyn Puzzle = &Lines;
+        assert_eq!(42, puzzle.day());
+        assert_eq!("Line Counter", puzzle.name());
+        assert_eq!("2", puzzle.solve(Part::One, "ab\ncde")?);
+        assert_eq!(
+            ["2".to_string(), "5".to_string()],
+            puzzle.solve_both("ab\ncde")?
+        );
+        let [one, two] = puzzle.run_both("ab\ncde")?;
+        assert_eq!((Part::One, "2"), (one.part, one.answer.as_str()));
+        assert_eq!((Part::Two, "5"), (two.part, two.answer.as_str()));
+        assert_eq!(one.parse, two.parse);
+        assert_eq!("5", puzzle.solve(Part::Two, "\u{feff}ab \r\ncde\r\n\r\n")?);
+        assert_eq!(2, puzzle.validate(Part::One, "ab\ncde")?.lines);
+        assert!(puzzle.validate(Part::Two, "\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_stages() -> miette::Result<()> {
+        let mut visited = vec![];
+        Lines.stages(["a", "bc\nd"], &mut |stage, run| {
+            run()?;
+            visited.push(stage);
+            Ok(())
+        })?;
+        assert_eq!(Stage::ALL.to_vec(), visited);
+        Ok(())
+    }
+}
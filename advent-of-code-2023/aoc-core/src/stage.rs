@@ -0,0 +1,58 @@
+use std::{fmt, str::FromStr};
+
+use crate::{part::ParsePartError, Part};
+
+/// A measurable step of a day: parsing its input or solving one part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [
+        Stage::Parse,
+        Stage::Solve(Part::One),
+        Stage::Solve(Part::Two),
+    ];
+
+    /// The input this stage runs on: parsing uses the first part's input.
+    pub fn input(self, inputs: [&str; 2]) -> &str {
+        match self {
+            Stage::Parse => inputs[0],
+            Stage::Solve(part) => inputs[part.index()],
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "{part}"),
+        }
+    }
+}
+
+impl FromStr for Stage {
+    type Err = ParsePartError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            part => part.parse().map(Stage::Solve),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_round_trip() {
+        for stage in Stage::ALL {
+            assert_eq!(Ok(stage), stage.to_string().parse());
+        }
+    }
+}
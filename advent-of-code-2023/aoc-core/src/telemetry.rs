@@ -0,0 +1,66 @@
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::{
+    filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer,
+};
+
+use crate::cli::Args;
+
+/// Log level for a number of `-v` flags: info by default, then debug and trace.
+pub fn level(verbose: u8) -> LevelFilter {
+    match verbose {
+        0 => LevelFilter::INFO,
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// Keeps the trace outputs open; they are flushed when this is dropped.
+#[must_use = "the Chrome trace is only written out when this is dropped"]
+pub struct Telemetry {
+    _chrome: Option<FlushGuard>,
+}
+
+/// Installs the global tracing subscriber of a part binary, logging to stderr
+/// at the level picked by `RUST_LOG` or, when unset, by `args.verbose`.
+///
+/// With `--chrome-trace` the spans are also written as Chrome trace-event JSON.
+/// With the `tracy` feature every span is also streamed to a Tracy profiler.
+/// Run with `TRACY_NO_EXIT=1` so the process waits for the profiler to collect
+/// them before exiting.
+pub fn init(args: &Args) -> Telemetry {
+    let filter = EnvFilter::builder()
+        .with_default_directive(level(args.verbose).into())
+        .from_env_lossy();
+    let (chrome, guard) = match &args.chrome_trace {
+        Some(path) => {
+            if let Some(dir) = path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            let (layer, guard) = ChromeLayerBuilder::new()
+                .file(path)
+                .include_args(true)
+                .build();
+            (Some(layer.with_filter(level(args.verbose))), Some(guard))
+        }
+        None => (None, None),
+    };
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(filter),
+        )
+        .with(chrome);
+    #[cfg(feature = "tracy")]
+    let subscriber = subscriber.with(tracing_tracy::TracyLayer::default());
+    subscriber.init();
+    Telemetry { _chrome: guard }
+}
+
+/// Ends the current Tracy frame, so each solved part shows up as its own frame.
+pub fn frame_mark() {
+    #[cfg(feature = "tracy")]
+    if let Some(client) = tracing_tracy::client::Client::running() {
+        client.frame_mark();
+    }
+}
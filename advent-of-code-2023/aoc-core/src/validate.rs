@@ -0,0 +1,239 @@
+//! Checks of the shape of a puzzle input, run before solving so that a
+//! truncated download or a hand-edited file is reported with the line at
+//! fault instead of as a wrong answer.
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::input::strip_bom;
+
+/// How the lines of an input end.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "report",
+    derive(serde::Serialize),
+    serde(rename_all = "UPPERCASE")
+)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl fmt::Display for LineEnding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LineEnding::Lf => "LF",
+            LineEnding::CrLf => "CRLF",
+        })
+    }
+}
+
+/// What an input looks like, as reported once it is validated.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "report", derive(serde::Serialize))]
+pub struct InputStats {
+    pub bytes: usize,
+    pub lines: usize,
+    /// Length of the longest line, without its line ending.
+    pub width: usize,
+    pub line_ending: LineEnding,
+    /// Number of distinct bytes, line endings aside.
+    pub alphabet: usize,
+}
+
+impl fmt::Display for InputStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} lines, {} bytes, up to {} wide, {} line endings, {} distinct characters",
+            self.lines, self.bytes, self.width, self.line_ending, self.alphabet
+        )
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid input on line {line}: {reason}")]
+pub struct InvalidInput {
+    pub line: usize,
+    pub reason: String,
+}
+
+impl InvalidInput {
+    pub fn new(line: usize, reason: impl Into<String>) -> Self {
+        InvalidInput {
+            line,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// The 1-based number and content of each line of `input`, without its line
+/// ending.
+pub fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+}
+
+/// Measures `input` as it was given, before [`crate::normalize`], checking
+/// that it is not empty and that its lines all end the same way. The last
+/// line may have no line ending at all.
+pub fn stats(input: &str) -> Result<InputStats, InvalidInput> {
+    let mut stats = InputStats {
+        bytes: input.len(),
+        ..InputStats::default()
+    };
+    let input = strip_bom(input);
+    if input.trim().is_empty() {
+        return Err(InvalidInput::new(1, "the input is empty"));
+    }
+    let mut seen = [false; 256];
+    let mut ending = None;
+    for (number, line) in input.split_inclusive('\n').enumerate() {
+        let number = number + 1;
+        let (content, this_ending) = match line.strip_suffix('\n') {
+            Some(line) => match line.strip_suffix('\r') {
+                Some(line) => (line, Some(LineEnding::CrLf)),
+                None => (line, Some(LineEnding::Lf)),
+            },
+            None => (line, None),
+        };
+        if content.contains('\r') {
+            return Err(InvalidInput::new(number, "stray carriage return"));
+        }
+        match (ending, this_ending) {
+            (Some(expected), Some(found)) if expected != found => {
+                return Err(InvalidInput::new(
+                    number,
+                    format!("ends with {found} where the lines above end with {expected}"),
+                ))
+            }
+            (None, Some(found)) => ending = Some(found),
+            _ => {}
+        }
+        stats.lines = number;
+        stats.width = stats.width.max(content.len());
+        for &byte in content.as_bytes() {
+            seen[usize::from(byte)] = true;
+        }
+    }
+    stats.line_ending = ending.unwrap_or_default();
+    stats.alphabet = seen.iter().filter(|&&seen| seen).count();
+    Ok(stats)
+}
+
+/// Checks that every byte of `input` but line endings is `allowed`, which is
+/// described as `expected` in the error.
+pub fn alphabet(
+    input: &str,
+    allowed: impl Fn(u8) -> bool,
+    expected: &str,
+) -> Result<(), InvalidInput> {
+    for (number, line) in lines(input) {
+        if let Some((column, &byte)) = line
+            .as_bytes()
+            .iter()
+            .enumerate()
+            .find(|(_, &byte)| !allowed(byte))
+        {
+            return Err(InvalidInput::new(
+                number,
+                format!(
+                    "unexpected `{}` in column {}, expected {expected}",
+                    char::from(byte).escape_default(),
+                    column + 1
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every line of `input` is as long as the first one.
+pub fn rectangular(input: &str) -> Result<(), InvalidInput> {
+    let mut lines = lines(input);
+    let Some((_, first)) = lines.next() else {
+        return Ok(());
+    };
+    match lines.find(|(_, line)| line.len() != first.len()) {
+        Some((number, line)) => Err(InvalidInput::new(
+            number,
+            format!(
+                "{} wide where the first line is {} wide",
+                line.len(),
+                first.len()
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() -> Result<(), InvalidInput> {
+        assert_eq!(
+            InputStats {
+                bytes: 12,
+                lines: 3,
+                width: 4,
+                line_ending: LineEnding::CrLf,
+                alphabet: 4,
+            },
+            stats("ab\r\nabcd\r\nba")?
+        );
+        assert_eq!(LineEnding::Lf, stats("ab\n")?.line_ending);
+        let bom = stats("\u{feff}ab\n")?;
+        assert_eq!((6, 2), (bom.bytes, bom.alphabet));
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_errors() {
+        assert_eq!(
+            Err(InvalidInput::new(1, "the input is empty")),
+            stats(" \n")
+        );
+        assert_eq!(
+            Err(InvalidInput::new(2, "stray carriage return")),
+            stats("ab\na\rb\n")
+        );
+        assert_eq!(
+            Err(InvalidInput::new(
+                3,
+                "ends with LF where the lines above end with CRLF"
+            )),
+            stats("a\r\nb\r\nc\nd")
+        );
+    }
+
+    #[test]
+    fn test_alphabet() {
+        let digits = |input| alphabet(input, |byte| byte.is_ascii_digit(), "a digit");
+        assert_eq!(Ok(()), digits("12\r\n34\r\n"));
+        assert_eq!(
+            Err(InvalidInput::new(
+                2,
+                "unexpected `\\t` in column 2, expected a digit"
+            )),
+            digits("12\n3\t4\n")
+        );
+    }
+
+    #[test]
+    fn test_rectangular() {
+        assert_eq!(Ok(()), rectangular("..\r\n.#\n"));
+        assert_eq!(
+            Err(InvalidInput::new(
+                3,
+                "1 wide where the first line is 2 wide"
+            )),
+            rectangular("..\n.#\n.\n")
+        );
+    }
+}
@@ -0,0 +1,54 @@
+pub mod registry;
+
+use aoc_core::Part;
+use wasm_bindgen::prelude::*;
+
+/// Solves `part` (1 or 2) of `day` for `input`, throwing on a bad input.
+/// Each day also exports its parts on their own, e.g. `day03_part2(input)`.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, JsError> {
+    run(day, part, input).map_err(|error| JsError::new(&error))
+}
+
+/// The registered days, in order.
+#[wasm_bindgen]
+pub fn days() -> Vec<u8> {
+    registry::DAYS.iter().map(|puzzle| puzzle.day()).collect()
+}
+
+/// [`solve`], with errors as text for callers outside of JavaScript.
+pub fn run(day: u8, part: u8, input: &str) -> Result<String, String> {
+    let puzzle = registry::find(day).ok_or_else(|| format!("day {day} is not registered"))?;
+    let part = part
+        .to_string()
+        .parse::<Part>()
+        .map_err(|error| error.to_string())?;
+    puzzle
+        .solve(part, input)
+        .map_err(|error| format!("{error:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let input = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+        assert_eq!(Ok("467835".to_string()), run(3, 2, input));
+        assert_eq!(
+            Err("day 25 is not registered".to_string()),
+            run(25, 1, input)
+        );
+        assert!(run(3, 3, input).is_err());
+    }
+}
@@ -0,0 +1,9 @@
+use aoc_core::Puzzle;
+
+/// The days built to WASM, kept in step with the `aoc` runner's by
+/// `cargo xtask new`.
+pub static DAYS: &[&dyn Puzzle] = &[&day_01::Day01, &day_02::Day02, &day_03::Day03];
+
+pub fn find(day: u8) -> Option<&'static dyn Puzzle> {
+    DAYS.iter().copied().find(|puzzle| puzzle.day() == day)
+}
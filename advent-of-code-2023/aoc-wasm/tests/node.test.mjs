@@ -0,0 +1,42 @@
+// Runs the WASM build of every day against `answers.txt` in Node:
+//
+//     wasm-pack build aoc-wasm --target nodejs --dev
+//     node --test aoc-wasm/tests/
+import { test } from "node:test";
+import assert from "node:assert/strict";
+import { readFileSync } from "node:fs";
+
+import * as aoc from "../pkg/aoc_wasm.js";
+
+const root = new URL("../../", import.meta.url);
+const read = (path) => readFileSync(new URL(path, root), "utf8");
+
+// 3 2 78272573
+const answers = read("answers.txt")
+  .split("\n")
+  .map((line) => line.trim())
+  .filter((line) => line && !line.startsWith("#"))
+  .map((line) => line.split(/\s+/))
+  .map(([day, part, answer]) => ({ day: Number(day), part: Number(part), answer }))
+  .filter(({ day }) => aoc.days().includes(day));
+
+const input = (day, part) =>
+  read(`day-${String(day).padStart(2, "0")}/input${part}.txt`);
+
+test("solve matches the verified answers", () => {
+  for (const { day, part, answer } of answers) {
+    assert.equal(aoc.solve(day, part, input(day, part)), answer, `day ${day} part ${part}`);
+  }
+});
+
+test("each part is exported on its own", () => {
+  for (const { day, part, answer } of answers) {
+    const name = `day${String(day).padStart(2, "0")}_part${part}`;
+    assert.equal(aoc[name](input(day, part)), answer, name);
+  }
+});
+
+test("errors are thrown", () => {
+  assert.throws(() => aoc.solve(25, 1, ""), /day 25 is not registered/);
+  assert.throws(() => aoc.day02_part1("Game one: 3 blue"));
+});
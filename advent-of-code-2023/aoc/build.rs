@@ -0,0 +1,67 @@
+//! Hashes the sources the answers depend on, the day crates and `aoc-core`,
+//! into `AOC_BUILD_ID`, so that cached answers are dropped when they change.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+fn main() -> io::Result<()> {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let root = manifest_dir.parent().unwrap();
+    println!("cargo:rerun-if-changed=Cargo.toml");
+
+    let mut crates = vec![root.join("aoc-core")];
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        let is_day = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("day-"));
+        if is_day && path.join("Cargo.toml").is_file() {
+            crates.push(path);
+        }
+    }
+
+    let mut files = vec![];
+    for krate in &crates {
+        for watched in [krate.join("Cargo.toml"), krate.join("src")] {
+            println!("cargo:rerun-if-changed={}", watched.display());
+            collect(&watched, &mut files)?;
+        }
+    }
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in &files {
+        let relative = file.strip_prefix(root).unwrap_or(file);
+        hasher.update(relative.to_string_lossy().replace('\\', "/"));
+        hasher.update([0]);
+        hasher.update(fs::read(file)?);
+        hasher.update([0]);
+    }
+    let id: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    println!("cargo:rustc-env=AOC_BUILD_ID={id}");
+    Ok(())
+}
+
+/// `path` itself when it is a file, else every file below it.
+fn collect(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    if !path.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(path)? {
+        collect(&entry?.path(), files)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use aoc_core::Part;
+
+use crate::{custom_error::AocError, input::workspace_root};
+
+/// Verified answers keyed by day and part, read from `answers.txt`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, Part), String>);
+
+impl Answers {
+    pub fn path() -> PathBuf {
+        workspace_root().join("answers.txt")
+    }
+
+    pub fn load() -> Result<Self, AocError> {
+        Self::parse(&std::fs::read_to_string(Self::path())?)
+    }
+
+    // 3 2 78272573
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let mut answers = BTreeMap::new();
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad_answer = || AocError::BadAnswer {
+                line: index + 1,
+                content: line.to_string(),
+            };
+            let mut fields = line.split_whitespace();
+            let (Some(day), Some(part), Some(answer), None) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(bad_answer());
+            };
+            let day = day.parse::<u8>().map_err(|_| bad_answer())?;
+            let part = part.parse::<Part>().map_err(|_| bad_answer())?;
+            answers.insert((day, part), answer.to_string());
+        }
+        Ok(Answers(answers))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Saves a verified answer to `answers.txt`, see [`Answers::with_answer`].
+    pub fn record(day: u8, part: Part, answer: &str) -> Result<(), AocError> {
+        let answers = std::fs::read_to_string(Self::path())?;
+        std::fs::write(Self::path(), Self::with_answer(&answers, day, part, answer))?;
+        Ok(())
+    }
+
+    /// `answers` with the answer of a part set, in place of its previous
+    /// answer or the `# <day> <part> <answer>` placeholder left for it.
+    pub fn with_answer(answers: &str, day: u8, part: Part, answer: &str) -> String {
+        let key = format!("{day} {}", part.number());
+        let line = format!("{key} {answer}");
+        let mut lines = answers.lines().map(str::to_string).collect::<Vec<_>>();
+        let existing = lines.iter().position(|line| {
+            let line = line.trim_start_matches('#').trim_start();
+            line.strip_prefix(&key)
+                .is_some_and(|rest| rest.starts_with(' '))
+        });
+        match existing {
+            Some(index) => lines[index] = line,
+            None => lines.push(line),
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let answers = Answers::parse(
+            "# day part answer
+1 1 142
+
+1 2 281",
+        )?;
+        assert_eq!(Some("142"), answers.get(1, Part::One));
+        assert_eq!(Some("281"), answers.get(1, Part::Two));
+        assert_eq!(None, answers.get(2, Part::One));
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_answer() -> miette::Result<()> {
+        let answers = "# day part answer\n1 1 142\n# 4 1 <answer>\n# 4 2 <answer>\n";
+        let answers = Answers::with_answer(answers, 4, Part::One, "13");
+        assert_eq!(
+            "# day part answer\n1 1 142\n4 1 13\n# 4 2 <answer>\n",
+            answers
+        );
+        let answers = Answers::with_answer(&answers, 1, Part::Two, "281");
+        assert_eq!(Some("281"), Answers::parse(&answers)?.get(1, Part::Two));
+        assert!(answers.ends_with("1 2 281\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        assert!(matches!(
+            Answers::parse("1 3 142"),
+            Err(AocError::BadAnswer { line: 1, .. })
+        ));
+    }
+}
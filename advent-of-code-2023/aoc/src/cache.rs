@@ -0,0 +1,193 @@
+use std::{fmt::Write, path::PathBuf};
+
+use aoc_core::Part;
+
+use crate::{client, custom_error::AocError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cached {
+    pub day: u8,
+    pub part: Part,
+    pub input_sha256: String,
+    pub answer: String,
+}
+
+/// Answers of earlier runs, read from `.aoc-cache/answers.txt`. They are only
+/// trusted for the same input and the same build of the runner, answers of
+/// other builds are dropped when the cache is saved.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Cache {
+    build: String,
+    answers: Vec<Cached>,
+}
+
+impl Cache {
+    pub fn path() -> PathBuf {
+        client::cache_dir().join("answers.txt")
+    }
+
+    /// What the answers depend on besides the input: the hash of the sources
+    /// of the days and of `aoc-core`, taken by `build.rs` when compiling.
+    pub fn build_id() -> &'static str {
+        env!("AOC_BUILD_ID")
+    }
+
+    /// The answers cached for this build, none before the first run.
+    pub fn load() -> Result<Self, AocError> {
+        let build = Self::build_id().to_string();
+        match std::fs::read_to_string(Self::path()) {
+            Ok(cache) => Ok(Self::parse(&cache, build)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Cache {
+                build,
+                ..Cache::default()
+            }),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), AocError> {
+        client::write_cache(&Self::path(), &self.render())
+    }
+
+    /// The lines of `answers.txt`, read back by [`Cache::parse`].
+    pub fn render(&self) -> String {
+        let mut cache = String::from("# day part input_sha256 build answer\n");
+        for cached in &self.answers {
+            let Cached {
+                day,
+                part,
+                input_sha256,
+                answer,
+            } = cached;
+            let _ = writeln!(
+                cache,
+                "{day} {} {input_sha256} {} {answer}",
+                part.number(),
+                self.build
+            );
+        }
+        cache
+    }
+
+    // 1 1 ab0395e7... 5d41402a... 52974
+    /// Keeps the answers of `build`. Malformed lines are skipped, the cache
+    /// can always be rebuilt.
+    pub fn parse(input: &str, build: String) -> Self {
+        let answers = input
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.splitn(5, ' ');
+                let (Some(day), Some(part), Some(input_sha256), Some(line_build), Some(answer)) = (
+                    fields.next(),
+                    fields.next(),
+                    fields.next(),
+                    fields.next(),
+                    fields.next(),
+                ) else {
+                    return None;
+                };
+                if line_build != build {
+                    return None;
+                }
+                Some(Cached {
+                    day: day.parse().ok()?,
+                    part: part.parse().ok()?,
+                    input_sha256: input_sha256.to_string(),
+                    answer: answer.to_string(),
+                })
+            })
+            .collect();
+        Cache { build, answers }
+    }
+
+    pub fn get(&self, day: u8, part: Part, input_sha256: &str) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|cached| {
+                cached.day == day && cached.part == part && cached.input_sha256 == input_sha256
+            })
+            .map(|cached| cached.answer.as_str())
+    }
+
+    /// Caches `answer`, replacing the one of an earlier input of the part.
+    pub fn insert(&mut self, day: u8, part: Part, input_sha256: &str, answer: &str) {
+        self.answers
+            .retain(|cached| cached.day != day || cached.part != part);
+        self.answers.push(Cached {
+            day,
+            part,
+            input_sha256: input_sha256.to_string(),
+            answer: answer.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::{input, registry};
+
+    const CACHE: &str = "# day part input_sha256 build answer
+1 1 aaaa build1 52974
+1 2 aaaa build2 53340
+2 1 bbbb build1
+2 x bbbb build1 2476
+";
+
+    #[test]
+    fn test_parse() {
+        let cache = Cache::parse(CACHE, "build1".to_string());
+        assert_eq!(Some("52974"), cache.get(1, Part::One, "aaaa"));
+        assert_eq!(None, cache.get(1, Part::One, "cccc"));
+        assert_eq!(None, cache.get(1, Part::Two, "aaaa"));
+        assert_eq!(None, cache.get(2, Part::One, "bbbb"));
+        assert_eq!(
+            Some("53340"),
+            Cache::parse(CACHE, "build2".to_string()).get(1, Part::Two, "aaaa")
+        );
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut cache = Cache::parse(CACHE, "build1".to_string());
+        cache.insert(1, Part::One, "cccc", "two words");
+        cache.insert(3, Part::Two, "dddd", "78272573");
+        assert_eq!(None, cache.get(1, Part::One, "aaaa"));
+        assert_eq!(Some("two words"), cache.get(1, Part::One, "cccc"));
+        assert_eq!(Some("78272573"), cache.get(3, Part::Two, "dddd"));
+    }
+
+    #[test]
+    fn test_hit_costs_no_more_than_solving() -> miette::Result<()> {
+        let mut cache = Cache::parse("", Cache::build_id().to_string());
+        let mut solving = Duration::ZERO;
+        let mut hashes = vec![];
+        for puzzle in registry::DAYS {
+            if !input::is_saved(puzzle.day()) {
+                continue;
+            }
+            for part in Part::ALL {
+                let input = input::load(puzzle.day(), part)?;
+                let start = Instant::now();
+                let answer = puzzle.solve(part, &input)?;
+                solving += start.elapsed();
+                let input_sha256 = aoc_core::report::sha256(&input);
+                cache.insert(puzzle.day(), part, &input_sha256, &answer);
+                hashes.push((puzzle.day(), part, input_sha256));
+            }
+        }
+        let saved = cache.render();
+
+        let start = Instant::now();
+        let cache = Cache::parse(&saved, Cache::build_id().to_string());
+        for (day, part, input_sha256) in &hashes {
+            assert!(cache.get(*day, *part, input_sha256).is_some());
+        }
+        let hit = start.elapsed();
+        assert!(hit <= solving, "hit {hit:?}, solving {solving:?}");
+        Ok(())
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use aoc_core::Part;
+
+use crate::{custom_error::AocError, input::workspace_root};
+
+pub const YEAR: u16 = 2023;
+pub const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/AbdesamedBendjeddou/advent-of-code by ureq";
+
+/// What the puzzle server said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint of which way.
+    Wrong,
+    /// Submitted too soon after a wrong answer, nothing was checked.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part was already solved, or part 1 is not solved yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    // That's not the right answer; your answer is too high.  If you're stuck, ...
+    pub fn parse(page: &str) -> Option<Verdict> {
+        let text = match (page.find("<article>"), page.find("</article>")) {
+            (Some(start), Some(end)) if start < end => &page[start..end],
+            _ => page,
+        };
+        if text.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if text.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if text.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else if text.contains("You gave an answer too recently") {
+            Some(Verdict::RateLimited {
+                wait: parse_wait(text),
+            })
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("correct"),
+            Verdict::TooHigh => f.write_str("too high"),
+            Verdict::TooLow => f.write_str("too low"),
+            Verdict::Wrong => f.write_str("wrong"),
+            Verdict::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+            Verdict::RateLimited { wait: None } => f.write_str("rate limited"),
+            Verdict::WrongLevel => f.write_str("wrong level"),
+        }
+    }
+}
+
+/// Parses the verdicts that say something about the answer, as displayed.
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too high" => Ok(Verdict::TooHigh),
+            "too low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+// You have 1m 5s left to wait.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let end = text.find(" left to wait")?;
+    let start = text[..end].rfind("You have ")? + "You have ".len();
+    text[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let number = number.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Talks to the puzzle server with a session cookie, keeping what it fetched
+/// in a disk cache so each input is only downloaded once.
+#[derive(Debug)]
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    cache: Option<PathBuf>,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Client {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into().trim().to_string(),
+            cache: None,
+        }
+    }
+
+    /// Caches fetched pages under `dir`.
+    pub fn with_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache = Some(dir.into());
+        self
+    }
+
+    /// A client for `AOC_URL`, by default the real server, logged in with
+    /// `AOC_SESSION` or the contents of `.session`, caching in `.aoc-cache`.
+    pub fn from_env() -> Result<Self, AocError> {
+        let session = match std::env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => std::fs::read_to_string(session_path()).map_err(|_| AocError::NoSession)?,
+        };
+        if session.trim().is_empty() {
+            return Err(AocError::NoSession);
+        }
+        let base_url = std::env::var("AOC_URL").unwrap_or_else(|_| BASE_URL.to_string());
+        Ok(Client::new(base_url, session).with_cache(cache_dir()))
+    }
+
+    /// The puzzle input of `day`, shared by both parts.
+    pub fn fetch_input(&self, day: u8) -> Result<String, AocError> {
+        self.cached(
+            &format!("day-{day:02}/input.txt"),
+            |_| true,
+            || self.get(&format!("/{YEAR}/day/{day}/input")),
+        )
+    }
+
+    /// The puzzle page of `day`. A cached page is fetched again until it
+    /// describes both parts, as part 2 only shows once part 1 is solved.
+    pub fn fetch_puzzle(&self, day: u8) -> Result<String, AocError> {
+        let complete = |page: &str| page.matches("<article").count() >= 2;
+        self.cached(&format!("day-{day:02}/puzzle.html"), complete, || {
+            self.get(&format!("/{YEAR}/day/{day}"))
+        })
+    }
+
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Verdict, AocError> {
+        let url = format!("{}/{YEAR}/day/{day}/answer", self.base_url);
+        let level = part.number().to_string();
+        let page = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &level), ("answer", answer)])
+            .map_err(|error| http_error(&url, error))?
+            .into_string()?;
+        Verdict::parse(&page).ok_or_else(|| AocError::UnexpectedResponse {
+            url,
+            page: page.chars().take(200).collect(),
+        })
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocError> {
+        let url = format!("{}{path}", self.base_url);
+        Ok(self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|error| http_error(&url, error))?
+            .into_string()?)
+    }
+
+    /// `key` from the cache if it is `complete`, otherwise from `fetch` and
+    /// then cached.
+    fn cached(
+        &self,
+        key: &str,
+        complete: impl Fn(&str) -> bool,
+        fetch: impl FnOnce() -> Result<String, AocError>,
+    ) -> Result<String, AocError> {
+        let Some(path) = self.cache.as_ref().map(|dir| dir.join(key)) else {
+            return fetch();
+        };
+        if let Ok(page) = std::fs::read_to_string(&path) {
+            if complete(&page) {
+                return Ok(page);
+            }
+        }
+        let page = fetch()?;
+        write_cache(&path, &page)?;
+        Ok(page)
+    }
+}
+
+pub fn write_cache(path: &Path, page: &str) -> Result<(), AocError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, page)?;
+    Ok(())
+}
+
+fn http_error(url: &str, error: ureq::Error) -> AocError {
+    let message = match error {
+        ureq::Error::Status(status, response) => {
+            let body = response.into_string().unwrap_or_default();
+            format!("{status} {}", body.trim())
+        }
+        ureq::Error::Transport(transport) => match transport.message() {
+            Some(message) => format!("{}: {message}", transport.kind()),
+            None => transport.kind().to_string(),
+        },
+    };
+    AocError::Http {
+        url: url.to_string(),
+        message,
+    }
+}
+
+pub fn session_path() -> PathBuf {
+    workspace_root().join(".session")
+}
+
+pub fn cache_dir() -> PathBuf {
+    workspace_root().join(".aoc-cache")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verdict() {
+        let page = |text: &str| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+        assert_eq!(
+            Some(Verdict::TooHigh),
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck, ..."
+            ))
+        );
+        assert_eq!(
+            Some(Verdict::Correct),
+            Verdict::parse(&page(
+                "That's the right answer!  You are one gold star closer."
+            ))
+        );
+        assert_eq!(
+            Some(Verdict::RateLimited {
+                wait: Some(Duration::from_secs(65))
+            }),
+            Verdict::parse(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+            ))
+        );
+        assert_eq!(None, Verdict::parse("<html>Please log in</html>"));
+    }
+}
@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+
+use aoc_core::Part;
+
+use crate::submissions::Refusal;
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("could not read {path}")]
+    #[diagnostic(code(aoc::missing_input), help("save the puzzle input to this path"))]
+    MissingInput {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("day {0} is not registered")]
+    #[diagnostic(code(aoc::unknown_day), help("add it to `registry::DAYS`"))]
+    UnknownDay(u8),
+
+    #[error("answers line {line} is malformed: `{content}`")]
+    #[diagnostic(code(aoc::bad_answers), help("expected `<day> <part> <answer>`"))]
+    BadAnswer { line: usize, content: String },
+
+    #[error("submissions line {line} is malformed: `{content}`")]
+    #[diagnostic(
+        code(aoc::bad_submissions),
+        help("expected `<day> <part> <answer> <correct|too high|too low|wrong>`")
+    )]
+    BadSubmission { line: usize, content: String },
+
+    #[error("not submitting {answer} for day {day} {part}: {reason}")]
+    #[diagnostic(code(aoc::refused), help("see `submissions.txt` for what was tried"))]
+    Refused {
+        day: u8,
+        part: Part,
+        answer: String,
+        reason: Refusal,
+    },
+
+    #[error("no puzzle description found for day {0}")]
+    #[diagnostic(
+        code(aoc::no_puzzle),
+        help(
+            "pass the page saved from adventofcode.com with `--html`, or check the session token"
+        )
+    )]
+    NoPuzzle(u8),
+
+    #[error("`git {command}` failed: {stderr}")]
+    #[diagnostic(code(aoc::git))]
+    Git { command: String, stderr: String },
+
+    #[error("no benchmark history for {0}")]
+    #[diagnostic(code(aoc::no_history), help("run `aoc bench` on that commit first"))]
+    NoHistory(String),
+
+    #[error("{0} benchmark regression(s) found")]
+    #[diagnostic(code(aoc::regression))]
+    Regressions(usize),
+
+    #[error("day {day} {part} answered {got}, expected {expected}")]
+    #[diagnostic(code(aoc::wrong_answer))]
+    WrongAnswer {
+        day: u8,
+        part: Part,
+        got: String,
+        expected: String,
+    },
+
+    #[error("no session token for the puzzle server")]
+    #[diagnostic(
+        code(aoc::no_session),
+        help("set `AOC_SESSION` or save the `session` cookie of adventofcode.com to `.session`")
+    )]
+    NoSession,
+
+    #[error("request to {url} failed: {message}")]
+    #[diagnostic(code(aoc::http))]
+    Http { url: String, message: String },
+
+    #[error("unexpected response from {url}: {page}")]
+    #[diagnostic(code(aoc::unexpected_response))]
+    UnexpectedResponse { url: String, page: String },
+}
@@ -0,0 +1,26 @@
+use std::process::Command;
+
+use crate::{custom_error::AocError, input::workspace_root};
+
+fn git(args: &[&str]) -> Result<String, AocError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(workspace_root())
+        .output()?;
+    if !output.status.success() {
+        return Err(AocError::Git {
+            command: args.join(" "),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Full hash of `rev`, e.g. `HEAD`, `main` or an abbreviated hash.
+pub fn rev_parse(rev: &str) -> Result<String, AocError> {
+    git(&["rev-parse", "--verify", &format!("{rev}^{{commit}}")])
+}
+
+pub fn is_dirty() -> Result<bool, AocError> {
+    Ok(!git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty())
+}
@@ -0,0 +1,52 @@
+use std::path::{Path, PathBuf};
+
+use aoc_core::Part;
+
+use crate::custom_error::AocError;
+
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner lives inside the workspace")
+}
+
+pub fn bench_history() -> PathBuf {
+    workspace_root().join("bench-history.jsonl")
+}
+
+pub fn day_dir(day: u8) -> PathBuf {
+    workspace_root().join(format!("day-{day:02}"))
+}
+
+pub fn path(day: u8, part: Part) -> PathBuf {
+    day_dir(day).join(format!("input{}.txt", part.number()))
+}
+
+pub fn load(day: u8, part: Part) -> Result<String, AocError> {
+    let path = path(day, part);
+    std::fs::read_to_string(&path).map_err(|source| AocError::MissingInput { path, source })
+}
+
+/// Whether both inputs of `day` are saved, which they are not yet for a day
+/// just created by `cargo xtask new`.
+pub fn is_saved(day: u8) -> bool {
+    Part::ALL.into_iter().all(|part| {
+        std::fs::read_to_string(path(day, part)).is_ok_and(|input| !input.trim().is_empty())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        assert!(path(3, Part::Two).ends_with("day-03/input2.txt"));
+    }
+
+    #[test]
+    fn test_is_saved() {
+        assert!(is_saved(1));
+        assert!(!is_saved(0));
+    }
+}
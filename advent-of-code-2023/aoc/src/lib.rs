@@ -0,0 +1,9 @@
+pub mod answers;
+pub mod cache;
+pub mod client;
+pub mod custom_error;
+pub mod git;
+pub mod input;
+pub mod puzzle;
+pub mod registry;
+pub mod submissions;
@@ -0,0 +1,346 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc::{
+    answers::Answers,
+    cache::Cache,
+    client::{self, Client, Verdict},
+    custom_error::AocError,
+    git, input, puzzle, registry,
+    submissions::Submissions,
+};
+use aoc_bench::{
+    alloc::CountingAlloc,
+    harness,
+    history::{self, Entry, History},
+    report,
+};
+use aoc_core::{
+    report::{sha256, Format, Report},
+    Part, Puzzle,
+};
+use clap::{Parser, Subcommand};
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[derive(Parser, Debug)]
+#[command(name = "aoc", about = "Run the registered Advent of Code 2023 days")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve one day, or a single part of it
+    Run {
+        day: u8,
+        part: Option<Part>,
+        #[command(flatten)]
+        options: Options,
+    },
+    /// Solve every registered day
+    All {
+        #[command(flatten)]
+        options: Options,
+    },
+    /// Benchmark every registered day, or a single one, and record the
+    /// results for the current commit in `bench-history.jsonl`
+    Bench {
+        day: Option<u8>,
+        #[arg(long, default_value_t = 100)]
+        samples: u32,
+        /// Where to write the JSON report
+        #[arg(long, default_value = "benchmarks.json")]
+        out: PathBuf,
+    },
+    /// Compare the latest benchmark run against a baseline commit, failing
+    /// when a stage got slower or allocates more
+    Compare {
+        /// Defaults to the most recent run of another commit
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Allowed growth in percent before a stage counts as regressed
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Download the day's input from the puzzle server into `input1.txt`
+    /// and `input2.txt`, keeping inputs already saved there
+    Fetch { day: u8 },
+    /// Save the puzzle description to `day-XX/README.md` and its examples to
+    /// `day-XX/examples`, from the puzzle server or a saved page
+    Puzzle {
+        day: u8,
+        /// The puzzle page saved from the browser
+        #[arg(long)]
+        html: Option<PathBuf>,
+    },
+    /// Submit an answer, by default the one the day's solution gives. Answers
+    /// already known wrong, or outside the too high/too low bounds found so
+    /// far, are refused
+    Submit {
+        day: u8,
+        part: Part,
+        answer: Option<String>,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct Options {
+    /// How to print the answers: as a table, or as JSON or CSV along with
+    /// their input hash and validation stats
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+    /// Solve the inputs without first checking their shape
+    #[arg(long)]
+    no_validate: bool,
+    /// Solve again even when the answer is cached for the same input and
+    /// build
+    #[arg(long)]
+    force: bool,
+}
+
+fn main() -> miette::Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run { day, part, options } => {
+            let puzzle = registry::find(day).ok_or(AocError::UnknownDay(day))?;
+            let mut cache = Cache::load()?;
+            let parts = match part {
+                Some(part) => vec![part],
+                None => Part::ALL.to_vec(),
+            };
+            let reports = run_day(puzzle, &parts, &options, &mut cache)?;
+            cache.save()?;
+            print_reports(options.format, &reports);
+        }
+        Command::All { options } => {
+            let mut cache = Cache::load()?;
+            let mut reports = vec![];
+            for puzzle in registry::DAYS {
+                if !input::is_saved(puzzle.day()) {
+                    eprintln!(
+                        "day {:02} skipped, its input is not saved yet",
+                        puzzle.day()
+                    );
+                    continue;
+                }
+                reports.extend(run_day(*puzzle, &Part::ALL, &options, &mut cache)?);
+            }
+            cache.save()?;
+            print_reports(options.format, &reports);
+        }
+        Command::Bench { day, samples, out } => {
+            let puzzles = match day {
+                Some(day) => vec![registry::find(day).ok_or(AocError::UnknownDay(day))?],
+                None => registry::DAYS.to_vec(),
+            };
+            let config = harness::Config {
+                samples,
+                ..Default::default()
+            };
+            let mut records = vec![];
+            for puzzle in puzzles {
+                let inputs = Part::ALL.map(|part| input::load(puzzle.day(), part));
+                let [one, two] = inputs;
+                records.extend(harness::bench(puzzle, [&one?, &two?], &config)?);
+            }
+            print!("{}", report::table(&records));
+            std::fs::write(&out, report::to_json(&records)).map_err(AocError::from)?;
+
+            let mut history = History::load(&input::bench_history())?;
+            history.record(Entry {
+                commit: git::rev_parse("HEAD")?,
+                dirty: git::is_dirty()?,
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_secs()),
+                records,
+            });
+            history.save(&input::bench_history())?;
+        }
+        Command::Compare {
+            baseline,
+            threshold,
+        } => {
+            let history = History::load(&input::bench_history())?;
+            let current = history
+                .latest()
+                .ok_or_else(|| AocError::NoHistory("any commit".to_string()))?;
+            let baseline = match baseline {
+                Some(rev) => {
+                    let commit = git::rev_parse(&rev)?;
+                    history.find(&commit).ok_or(AocError::NoHistory(rev))?
+                }
+                None => history
+                    .previous(&current.commit)
+                    .ok_or_else(|| AocError::NoHistory("a previous commit".to_string()))?,
+            };
+            println!(
+                "comparing {}{} against baseline {}",
+                &current.commit[..current.commit.len().min(10)],
+                if current.dirty { " (dirty)" } else { "" },
+                &baseline.commit[..baseline.commit.len().min(10)],
+            );
+            let regressions = history::compare(baseline, current, threshold);
+            for regression in &regressions {
+                println!("{regression}");
+            }
+            if !regressions.is_empty() {
+                return Err(AocError::Regressions(regressions.len()).into());
+            }
+            println!("no regressions beyond {threshold}%");
+        }
+        Command::Fetch { day } => {
+            let client = Client::from_env()?;
+            let mut fetched = None;
+            for part in Part::ALL {
+                let path = input::path(day, part);
+                if std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+                    println!("kept {}", path.display());
+                    continue;
+                }
+                let input = match &fetched {
+                    Some(input) => input,
+                    None => fetched.insert(client.fetch_input(day)?),
+                };
+                std::fs::write(&path, input).map_err(AocError::from)?;
+                println!("saved {}", path.display());
+            }
+        }
+        Command::Puzzle { day, html } => {
+            let page = match html {
+                Some(path) => {
+                    let page = std::fs::read_to_string(path).map_err(AocError::from)?;
+                    client::write_cache(&puzzle::cache_path(day), &page)?;
+                    page
+                }
+                None => Client::from_env()?.fetch_puzzle(day)?,
+            };
+            for path in puzzle::write_day(&input::day_dir(day), day, &page)? {
+                println!("saved {}", path.display());
+            }
+        }
+        Command::Submit { day, part, answer } => {
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    let puzzle = registry::find(day).ok_or(AocError::UnknownDay(day))?;
+                    puzzle.solve(part, &input::load(day, part)?)?
+                }
+            };
+            let mut submissions = Submissions::load()?;
+            if let Err(reason) = submissions.check(day, part, &answer) {
+                return Err(AocError::Refused {
+                    day,
+                    part,
+                    answer,
+                    reason,
+                }
+                .into());
+            }
+            let verdict = Client::from_env()?.submit(day, part, &answer)?;
+            println!("day {day:02} {part} {answer}: {verdict}");
+            submissions.record(day, part, &answer, verdict);
+            submissions.save()?;
+            if verdict == Verdict::Correct {
+                Answers::record(day, part, &answer)?;
+                println!("saved to {}", Answers::path().display());
+            }
+            let (low, high) = submissions.bounds(day, part);
+            if verdict != Verdict::Correct && (low.is_some() || high.is_some()) {
+                let bound = |bound: Option<i64>| bound.map_or("?".to_string(), |b| b.to_string());
+                println!("answer is between {} and {}", bound(low), bound(high));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Solves `parts` of the day, taking the answers from `cache` unless forced
+/// and parsing only once when both parts are solved from the same input.
+fn run_day(
+    puzzle: &dyn Puzzle,
+    parts: &[Part],
+    options: &Options,
+    cache: &mut Cache,
+) -> miette::Result<Vec<Report>> {
+    let mut inputs = vec![];
+    let mut reports = vec![];
+    for &part in parts {
+        let input = input::load(puzzle.day(), part)?;
+        let mut report = new_report(puzzle, part, &input, options)?;
+        if let Some(answer) = cache.get(puzzle.day(), part, &report.input_sha256) {
+            report.answer = answer.to_string();
+            report.cached = !options.force;
+        }
+        inputs.push(input);
+        reports.push(report);
+    }
+    let missing = (0..parts.len())
+        .filter(|&index| !reports[index].cached)
+        .collect::<Vec<_>>();
+    let runs = match missing[..] {
+        [one, two] if inputs[one] == inputs[two] => puzzle
+            .run_both(&inputs[one])?
+            .into_iter()
+            .zip([one, two])
+            .collect(),
+        _ => missing
+            .iter()
+            .map(|&index| Ok((puzzle.run(parts[index], &inputs[index])?, index)))
+            .collect::<miette::Result<Vec<_>>>()?,
+    };
+    for (run, index) in runs {
+        let report = &mut reports[index];
+        cache.insert(puzzle.day(), run.part, &report.input_sha256, &run.answer);
+        report.answer = run.answer;
+        report.timed(run.parse, run.solve);
+    }
+    Ok(reports)
+}
+
+/// The report of `part` before it is solved, with the input validated unless
+/// asked not to.
+fn new_report(
+    puzzle: &dyn Puzzle,
+    part: Part,
+    input: &str,
+    options: &Options,
+) -> miette::Result<Report> {
+    let mut report = Report::new(puzzle.day(), part, puzzle.name());
+    report.input_sha256 = sha256(input);
+    if !options.no_validate {
+        report.validation = Some(puzzle.validate(part, input)?);
+    }
+    Ok(report)
+}
+
+fn print_reports(format: Format, reports: &[Report]) {
+    if format == Format::Csv {
+        println!("{}", Report::CSV_HEADER);
+    }
+    let nanos = |ns: Option<u64>| Duration::from_nanos(ns.unwrap_or_default());
+    for report in reports {
+        match format {
+            Format::Text if report.cached => println!(
+                "day {:02} part{} {:<16} {:>12}  cached",
+                report.day, report.part, report.name, report.answer,
+            ),
+            Format::Text => println!(
+                "day {:02} part{} {:<16} {:>12}  parse {:>10.2?}  solve {:>10.2?}",
+                report.day,
+                report.part,
+                report.name,
+                report.answer,
+                nanos(report.parse_ns),
+                nanos(report.solve_ns),
+            ),
+            Format::Json => println!("{}", report.json()),
+            Format::Csv => println!("{}", report.csv()),
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    client::{self, BASE_URL},
+    custom_error::AocError,
+};
+
+/// Where the puzzle page of `day` is kept, next to its cached input.
+pub fn cache_path(day: u8) -> PathBuf {
+    client::cache_dir().join(format!("day-{day:02}/puzzle.html"))
+}
+
+/// The `<article class="day-desc">` of each part unlocked so far.
+pub fn articles(page: &str) -> Vec<&str> {
+    const START: &str = "<article class=\"day-desc\">";
+    let mut articles = vec![];
+    let mut rest = page;
+    while let Some(start) = rest.find(START) {
+        let body = &rest[start + START.len()..];
+        let end = body.find("</article>").unwrap_or(body.len());
+        articles.push(&body[..end]);
+        rest = &body[end..];
+    }
+    articles
+}
+
+/// The articles of `page` as Markdown, the first heading as its title.
+pub fn to_markdown(page: &str, day: u8) -> String {
+    let mut markdown = String::new();
+    for article in articles(page) {
+        markdown.push_str(&article_to_markdown(article));
+    }
+    let markdown = markdown.trim().replacen("## ", "# ", 1);
+    let url = format!("{BASE_URL}/{}/day/{day}", client::YEAR);
+    format!("{markdown}\n\n[Puzzle]({url}), [input]({url}/input)\n")
+}
+
+// <h2>--- Day 3: Gear Ratios ---</h2><p>You and the <em>Elf</em> ...</p>
+fn article_to_markdown(article: &str) -> String {
+    let mut markdown = String::new();
+    let (mut in_pre, mut in_code) = (false, false);
+    let mut links = vec![];
+    for token in tokens(article) {
+        let (name, closing, tag) = match token {
+            Token::Tag { name, closing, tag } => (name, closing, tag),
+            Token::Text(text) => {
+                let mut text = unescape(text);
+                // Whitespace between block tags is not part of the text.
+                if !in_pre && (markdown.is_empty() || markdown.ends_with('\n')) {
+                    text = text.trim_start().to_string();
+                }
+                if markdown.ends_with("## ") {
+                    markdown.push_str(text.trim_matches(|c| c == '-' || c == ' '));
+                } else {
+                    markdown.push_str(&text);
+                }
+                continue;
+            }
+        };
+        match (name, closing) {
+            ("h2", false) => markdown.push_str("## "),
+            ("h2" | "p" | "ul", true) => {
+                markdown.truncate(markdown.trim_end_matches('\n').len());
+                markdown.push_str("\n\n");
+            }
+            ("pre", false) => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => {
+                in_code = !closing;
+                markdown.push('`');
+            }
+            ("em", _) if !in_pre && !in_code => markdown.push('*'),
+            ("li", false) => markdown.push_str("- "),
+            ("li", true) => markdown.push('\n'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").map(absolute_url));
+                markdown.push('[');
+            }
+            ("a", true) => match links.pop().flatten() {
+                Some(href) => markdown.push_str(&format!("]({href})")),
+                None => markdown.push(']'),
+            },
+            _ => {}
+        }
+    }
+    markdown
+}
+
+/// The `<pre><code>` blocks of each article, as plain text.
+pub fn examples(page: &str) -> Vec<Vec<String>> {
+    articles(page)
+        .into_iter()
+        .map(|article| {
+            let mut examples = vec![];
+            let mut rest = article;
+            while let Some(start) = rest.find("<pre><code>") {
+                let body = &rest[start + "<pre><code>".len()..];
+                let end = body.find("</code></pre>").unwrap_or(body.len());
+                let text = tokens(&body[..end])
+                    .filter_map(|token| match token {
+                        Token::Text(text) => Some(unescape(text)),
+                        Token::Tag { .. } => None,
+                    })
+                    .collect::<String>();
+                examples.push(text);
+                rest = &body[end..];
+            }
+            examples
+        })
+        .collect()
+}
+
+/// Writes `README.md` in `dir` from the puzzle `page`, and the examples of
+/// each part to `examples/partN.txt`, then `partN-2.txt` and so on. Examples
+/// already filled in are kept, and part 2 reuses the first example of part 1
+/// when it has none of its own. Returns the files written.
+pub fn write_day(dir: &Path, day: u8, page: &str) -> Result<Vec<PathBuf>, AocError> {
+    if articles(page).is_empty() {
+        return Err(AocError::NoPuzzle(day));
+    }
+    let mut written = vec![];
+    let readme = dir.join("README.md");
+    std::fs::write(&readme, to_markdown(page, day))?;
+    written.push(readme);
+
+    let examples_dir = dir.join("examples");
+    std::fs::create_dir_all(&examples_dir)?;
+    let mut parts = examples(page);
+    if let [one, two] = &mut parts[..] {
+        if two.is_empty() {
+            two.extend(one.first().cloned());
+        }
+    }
+    for (part, examples) in parts.iter().enumerate() {
+        for (index, example) in examples.iter().enumerate() {
+            let name = match index {
+                0 => format!("part{}.txt", part + 1),
+                _ => format!("part{}-{}.txt", part + 1, index + 1),
+            };
+            let path = examples_dir.join(name);
+            if std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+                continue;
+            }
+            std::fs::write(&path, example)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    Tag {
+        name: &'a str,
+        closing: bool,
+        tag: &'a str,
+    },
+}
+
+fn tokens(html: &str) -> impl Iterator<Item = Token<'_>> {
+    let mut rest = html;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        if let Some(tag) = rest.strip_prefix('<') {
+            let end = tag.find('>').unwrap_or(tag.len());
+            let (tag, after) = (&tag[..end], tag.get(end + 1..).unwrap_or_default());
+            rest = after;
+            let closing = tag.starts_with('/');
+            let name = tag
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default();
+            return Some(Token::Tag { name, closing, tag });
+        }
+        let end = rest.find('<').unwrap_or(rest.len());
+        let text = &rest[..end];
+        rest = &rest[end..];
+        Some(Token::Text(text))
+    })
+}
+
+// a href="/2023/day/3/input" target="_blank"
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let value = &tag[tag.find(&format!("{name}=\""))? + name.len() + 2..];
+    Some(&value[..value.find('"')?])
+}
+
+fn absolute_url(href: &str) -> String {
+    match href.strip_prefix('/') {
+        Some(path) => format!("{BASE_URL}/{path}"),
+        None => href.to_string(),
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 3: Gear Ratios ---</h2><p>You and the Elf eventually reach a <a href="https://en.wikipedia.org/wiki/Gondola_lift" target="_blank">gondola lift</a> station.</p>
+<p>Here is an example engine schematic:</p>
+<pre><code>467..114..
+...*......
+..35..<em>633</em>.
+</code></pre>
+<ul>
+<li>Any number adjacent to a symbol is a <em>part number</em>, even <code>&lt;diagonally&gt;</code>.</li>
+</ul>
+<p>What is the sum? Its sum is <code><em>4361</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>536202</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>A <em>gear</em> is any <code>*</code> symbol. See <a href="/2023/day/3/input">your input</a>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_to_markdown() {
+        assert_eq!(
+            "# Day 3: Gear Ratios
+
+You and the Elf eventually reach a [gondola lift](https://en.wikipedia.org/wiki/Gondola_lift) station.
+
+Here is an example engine schematic:
+
+```
+467..114..
+...*......
+..35..633.
+```
+
+- Any number adjacent to a symbol is a *part number*, even `<diagonally>`.
+
+What is the sum? Its sum is `4361`.
+
+## Part Two
+
+A *gear* is any `*` symbol. See [your input](https://adventofcode.com/2023/day/3/input).
+
+[Puzzle](https://adventofcode.com/2023/day/3), [input](https://adventofcode.com/2023/day/3/input)
+",
+            to_markdown(PAGE, 3)
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            vec![
+                vec!["467..114..\n...*......\n..35..633.\n".to_string()],
+                vec![]
+            ],
+            examples(PAGE)
+        );
+    }
+
+    #[test]
+    fn test_write_day() -> miette::Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-puzzle-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("examples")).map_err(AocError::from)?;
+        std::fs::write(dir.join("examples/part1.txt"), "kept\n").map_err(AocError::from)?;
+
+        let written = write_day(&dir, 3, PAGE)?;
+        assert_eq!(
+            vec![dir.join("README.md"), dir.join("examples/part2.txt")],
+            written
+        );
+        let read = |path: &str| std::fs::read_to_string(dir.join(path)).unwrap();
+        assert_eq!("kept\n", read("examples/part1.txt"));
+        assert_eq!(
+            "467..114..\n...*......\n..35..633.\n",
+            read("examples/part2.txt")
+        );
+        assert!(matches!(
+            write_day(&dir, 3, "<p>Please log in</p>"),
+            Err(AocError::NoPuzzle(3))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use aoc_core::Puzzle;
+
+pub static DAYS: &[&dyn Puzzle] = &[&day_01::Day01, &day_02::Day02, &day_03::Day03];
+
+pub fn find(day: u8) -> Option<&'static dyn Puzzle> {
+    DAYS.iter().copied().find(|puzzle| puzzle.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_sorted_and_unique() {
+        assert!(DAYS.windows(2).all(|pair| pair[0].day() < pair[1].day()));
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(Some("Gear Ratios"), find(3).map(|puzzle| puzzle.name()));
+        assert!(find(25).is_none());
+    }
+}
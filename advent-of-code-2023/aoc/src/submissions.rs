@@ -0,0 +1,221 @@
+use std::{
+    fmt::{self, Write},
+    path::PathBuf,
+};
+
+use aoc_core::Part;
+
+use crate::{client::Verdict, custom_error::AocError, input::workspace_root};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Answers submitted so far and what the server said, read from
+/// `submissions.txt`. Only verdicts that say something about the answer are
+/// kept, rate limits are not.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Submissions(Vec<Submission>);
+
+/// Why an answer is not worth submitting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    Solved(String),
+    KnownWrong(Verdict),
+    /// At or above an answer that was too high.
+    AtLeast(i64),
+    /// At or below an answer that was too low.
+    AtMost(i64),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "already solved with {answer}"),
+            Refusal::KnownWrong(verdict) => write!(f, "already submitted, it was {verdict}"),
+            Refusal::AtLeast(high) => write!(f, "{high} was already too high"),
+            Refusal::AtMost(low) => write!(f, "{low} was already too low"),
+        }
+    }
+}
+
+impl Submissions {
+    pub fn path() -> PathBuf {
+        workspace_root().join("submissions.txt")
+    }
+
+    /// The log, empty before the first submission.
+    pub fn load() -> Result<Self, AocError> {
+        match std::fs::read_to_string(Self::path()) {
+            Ok(log) => Self::parse(&log),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), AocError> {
+        let mut log = String::from("# day part answer verdict\n");
+        for submission in &self.0 {
+            let Submission {
+                day,
+                part,
+                answer,
+                verdict,
+            } = submission;
+            let _ = writeln!(log, "{day} {} {answer} {verdict}", part.number());
+        }
+        std::fs::write(Self::path(), log)?;
+        Ok(())
+    }
+
+    // 1 1 53000 too high
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let mut submissions = vec![];
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad_submission = || AocError::BadSubmission {
+                line: index + 1,
+                content: line.to_string(),
+            };
+            let mut fields = line.splitn(4, ' ');
+            let (Some(day), Some(part), Some(answer), Some(verdict)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(bad_submission());
+            };
+            submissions.push(Submission {
+                day: day.parse().map_err(|_| bad_submission())?,
+                part: part.parse().map_err(|_| bad_submission())?,
+                answer: answer.to_string(),
+                verdict: verdict.parse().map_err(|_| bad_submission())?,
+            });
+        }
+        Ok(Submissions(submissions))
+    }
+
+    /// Submissions of one part, oldest first.
+    pub fn of(&self, day: u8, part: Part) -> impl Iterator<Item = &Submission> {
+        self.0
+            .iter()
+            .filter(move |submission| submission.day == day && submission.part == part)
+    }
+
+    /// The accepted answer of the part, if any.
+    pub fn verified(&self, day: u8, part: Part) -> Option<&str> {
+        self.of(day, part)
+            .find(|submission| submission.verdict == Verdict::Correct)
+            .map(|submission| submission.answer.as_str())
+    }
+
+    /// The range the answer must fall in, from the lowest answer that was too
+    /// high and the highest that was too low, both excluded.
+    pub fn bounds(&self, day: u8, part: Part) -> (Option<i64>, Option<i64>) {
+        let numbers = |verdict| {
+            self.of(day, part)
+                .filter(move |submission| submission.verdict == verdict)
+                .filter_map(|submission| submission.answer.parse::<i64>().ok())
+        };
+        (
+            numbers(Verdict::TooLow).max(),
+            numbers(Verdict::TooHigh).min(),
+        )
+    }
+
+    /// Whether `answer` could still be right, given what was submitted.
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Result<(), Refusal> {
+        if let Some(verified) = self.verified(day, part) {
+            return Err(Refusal::Solved(verified.to_string()));
+        }
+        if let Some(known) = self
+            .of(day, part)
+            .find(|submission| submission.answer == answer)
+        {
+            return Err(Refusal::KnownWrong(known.verdict));
+        }
+        let Ok(number) = answer.parse::<i64>() else {
+            return Ok(());
+        };
+        match self.bounds(day, part) {
+            (_, Some(high)) if number >= high => Err(Refusal::AtLeast(high)),
+            (Some(low), _) if number <= low => Err(Refusal::AtMost(low)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Logs `verdict` for `answer`, unless it says nothing about the answer.
+    pub fn record(&mut self, day: u8, part: Part, answer: &str, verdict: Verdict) {
+        if matches!(verdict, Verdict::RateLimited { .. } | Verdict::WrongLevel) {
+            return;
+        }
+        self.0.push(Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "# day part answer verdict
+1 1 60000 too high
+1 1 50000 too low
+1 1 55000 too high
+1 2 fifty wrong
+";
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let submissions = Submissions::parse(LOG)?;
+        assert_eq!(3, submissions.of(1, Part::One).count());
+        assert_eq!((Some(50000), Some(55000)), submissions.bounds(1, Part::One));
+        assert_eq!((None, None), submissions.bounds(1, Part::Two));
+        assert!(matches!(
+            Submissions::parse("1 1 42 maybe"),
+            Err(AocError::BadSubmission { line: 1, .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_check() -> miette::Result<()> {
+        let mut submissions = Submissions::parse(LOG)?;
+        assert_eq!(Ok(()), submissions.check(1, Part::One, "52974"));
+        assert_eq!(
+            Err(Refusal::KnownWrong(Verdict::TooLow)),
+            submissions.check(1, Part::One, "50000")
+        );
+        assert_eq!(
+            Err(Refusal::AtLeast(55000)),
+            submissions.check(1, Part::One, "58000")
+        );
+        assert_eq!(
+            Err(Refusal::AtMost(50000)),
+            submissions.check(1, Part::One, "42")
+        );
+        assert_eq!(
+            Err(Refusal::KnownWrong(Verdict::Wrong)),
+            submissions.check(1, Part::Two, "fifty")
+        );
+
+        submissions.record(1, Part::One, "52974", Verdict::RateLimited { wait: None });
+        assert_eq!(None, submissions.verified(1, Part::One));
+        submissions.record(1, Part::One, "52974", Verdict::Correct);
+        assert_eq!(Some("52974"), submissions.verified(1, Part::One));
+        assert_eq!(
+            Err(Refusal::Solved("52974".to_string())),
+            submissions.check(1, Part::One, "52975")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use aoc::{answers::Answers, custom_error::AocError, input, registry};
+use aoc_core::Part;
+
+#[test]
+fn test_registered_days_match_answers() -> miette::Result<()> {
+    let answers = Answers::load()?;
+    for puzzle in registry::DAYS {
+        for part in Part::ALL {
+            let Some(expected) = answers.get(puzzle.day(), part) else {
+                continue;
+            };
+            let got = puzzle.solve(part, &input::load(puzzle.day(), part)?)?;
+            if got != expected {
+                return Err(AocError::WrongAnswer {
+                    day: puzzle.day(),
+                    part,
+                    got,
+                    expected: expected.to_string(),
+                }
+                .into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The inputs as a Windows editor would save them solve the same.
+#[test]
+fn test_windows_inputs_match_answers() -> miette::Result<()> {
+    let answers = Answers::load()?;
+    for puzzle in registry::DAYS {
+        for part in Part::ALL {
+            let Some(expected) = answers.get(puzzle.day(), part) else {
+                continue;
+            };
+            let input = input::load(puzzle.day(), part)?;
+            let windows = format!("\u{feff}{}\r\n", input.replace('\n', " \r\n"));
+            puzzle.validate(part, &windows)?;
+            assert_eq!(
+                expected,
+                puzzle.solve(part, &windows)?,
+                "day {}",
+                puzzle.day()
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+mod mock;
+
+use std::{collections::BTreeMap, time::Duration};
+
+use aoc::{
+    answers::Answers,
+    client::{Client, Verdict},
+    custom_error::AocError,
+    input, puzzle,
+};
+use aoc_core::Part;
+use mock::{Fixture, MockServer, SESSION};
+
+/// Serves the committed input and verified answers of day 1.
+fn day_01() -> miette::Result<MockServer> {
+    let answers = Answers::load()?;
+    let answer = |part| answers.get(1, part).unwrap_or_default().to_string();
+    Ok(MockServer::start(BTreeMap::from([(
+        1,
+        Fixture {
+            input: input::load(1, Part::One)?,
+            answers: [answer(Part::One), answer(Part::Two)],
+        },
+    )])))
+}
+
+fn cache_dir(test: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-client-{test}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_fetch_input_is_cached() -> miette::Result<()> {
+    let server = day_01()?;
+    let cache = cache_dir("fetch");
+    let client = Client::new(&server.url, SESSION).with_cache(&cache);
+
+    let input = client.fetch_input(1)?;
+    assert_eq!(input::load(1, Part::One)?, input);
+    assert_eq!(input, client.fetch_input(1)?);
+    assert_eq!(vec!["GET /2023/day/1/input"], server.requests());
+    assert!(cache.join("day-01/input.txt").exists());
+
+    assert!(matches!(client.fetch_input(2), Err(AocError::Http { .. })));
+    std::fs::remove_dir_all(&cache).unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_fetch_input_needs_session() -> miette::Result<()> {
+    let server = day_01()?;
+    let client = Client::new(&server.url, "expired");
+    assert!(matches!(
+        client.fetch_input(1),
+        Err(AocError::Http { message, .. }) if message.starts_with("400 Puzzle inputs differ by user")
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_submit() -> miette::Result<()> {
+    let server = day_01()?;
+    let client = Client::new(&server.url, SESSION);
+    let answer = Answers::load()?
+        .get(1, Part::One)
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+
+    assert_eq!(
+        Verdict::Correct,
+        client.submit(1, Part::One, &answer.to_string())?
+    );
+    assert_eq!(
+        Verdict::TooHigh,
+        client.submit(1, Part::One, &(answer + 1).to_string())?
+    );
+    assert_eq!(
+        Verdict::TooLow,
+        client.submit(1, Part::One, &(answer - 1).to_string())?
+    );
+    assert_eq!(Verdict::Wrong, client.submit(1, Part::One, "fifty")?);
+    server.rate_limit(42);
+    assert_eq!(
+        Verdict::RateLimited {
+            wait: Some(Duration::from_secs(42))
+        },
+        client.submit(1, Part::One, &answer.to_string())?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fetch_puzzle_until_both_parts() -> miette::Result<()> {
+    let server = day_01()?;
+    let cache = cache_dir("puzzle");
+    let client = Client::new(&server.url, SESSION).with_cache(&cache);
+
+    assert_eq!(1, puzzle::articles(&client.fetch_puzzle(1)?).len());
+    let answer = Answers::load()?.get(1, Part::One).unwrap().to_string();
+    assert_eq!(Verdict::Correct, client.submit(1, Part::One, &answer)?);
+    let page = client.fetch_puzzle(1)?;
+    assert_eq!(2, puzzle::articles(&page).len());
+    assert_eq!(page, client.fetch_puzzle(1)?);
+    assert_eq!(
+        vec!["1abc2\n".to_string()],
+        puzzle::examples(&page).concat()
+    );
+    assert_eq!(
+        2,
+        server
+            .requests()
+            .iter()
+            .filter(|request| *request == "GET /2023/day/1")
+            .count()
+    );
+    std::fs::remove_dir_all(&cache).unwrap();
+    Ok(())
+}
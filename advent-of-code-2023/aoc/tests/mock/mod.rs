@@ -0,0 +1,168 @@
+//! A stand-in for the puzzle server, serving fixture inputs and judging
+//! answers the way adventofcode.com words it.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, Mutex},
+    thread::JoinHandle,
+};
+
+use tiny_http::{Method, Request, Response, Server};
+
+pub const SESSION: &str = "mock-session";
+
+/// A day the mock knows: its input and the answers of both parts.
+pub struct Fixture {
+    pub input: String,
+    pub answers: [String; 2],
+}
+
+#[derive(Default)]
+struct State {
+    /// Requests received, as `METHOD /path`.
+    requests: Vec<String>,
+    /// Seconds to wait left, answered to the next submission.
+    rate_limit: Option<u64>,
+    /// Days whose part 1 was answered, showing part 2 on their page.
+    solved: BTreeSet<u8>,
+}
+
+pub struct MockServer {
+    pub url: String,
+    server: Arc<Server>,
+    state: Arc<Mutex<State>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start(fixtures: BTreeMap<u8, Fixture>) -> Self {
+        let server = Arc::new(Server::http("127.0.0.1:0").expect("bind the mock server"));
+        let port = server
+            .server_addr()
+            .to_ip()
+            .expect("the mock listens on TCP")
+            .port();
+        let state = Arc::new(Mutex::new(State::default()));
+        let thread = std::thread::spawn({
+            let (server, state) = (Arc::clone(&server), Arc::clone(&state));
+            move || {
+                for request in server.incoming_requests() {
+                    respond(request, &fixtures, &state);
+                }
+            }
+        });
+        MockServer {
+            url: format!("http://127.0.0.1:{port}"),
+            server,
+            state,
+            thread: Some(thread),
+        }
+    }
+
+    /// Answers the next submission with a rate limit of `seconds`.
+    pub fn rate_limit(&self, seconds: u64) {
+        self.state.lock().unwrap().rate_limit = Some(seconds);
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn respond(mut request: Request, fixtures: &BTreeMap<u8, Fixture>, state: &Mutex<State>) {
+    let (method, url) = (request.method().clone(), request.url().to_string());
+    state
+        .lock()
+        .unwrap()
+        .requests
+        .push(format!("{method} {url}"));
+
+    let logged_in = request.headers().iter().any(|header| {
+        header.field.equiv("Cookie") && header.value.as_str() == format!("session={SESSION}")
+    });
+    let mut body = String::new();
+    let _ = request.as_reader().read_to_string(&mut body);
+
+    // /2023/day/3/input
+    let route = url.split('/').skip(1).collect::<Vec<_>>();
+    let (day, fixture) = match route[..] {
+        ["2023", "day", day, ..] => match day.parse::<u8>() {
+            Ok(day) => (day, fixtures.get(&day)),
+            Err(_) => (0, None),
+        },
+        _ => (0, None),
+    };
+    let (status, page) = match (fixture, route.get(3..).unwrap_or_default(), method) {
+        (None, ..) => (404, "404 Not Found".to_string()),
+        (Some(_), [], Method::Get) => (200, puzzle(day, state)),
+        (Some(_), ..) if !logged_in => (
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string(),
+        ),
+        (Some(fixture), ["input"], Method::Get) => (200, fixture.input.clone()),
+        (Some(fixture), ["answer"], Method::Post) => {
+            (200, article(&judge(day, fixture, &body, state)))
+        }
+        _ => (405, "405 Method Not Allowed".to_string()),
+    };
+    let _ = request.respond(Response::from_string(page).with_status_code(status));
+}
+
+// level=1&answer=142
+fn judge(day: u8, fixture: &Fixture, form: &str, state: &Mutex<State>) -> String {
+    if let Some(seconds) = state.lock().unwrap().rate_limit.take() {
+        return format!(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {seconds}s left to wait."
+        );
+    }
+    let field = |name: &str| {
+        form.split('&')
+            .find_map(|pair| pair.strip_prefix(&format!("{name}=")))
+            .unwrap_or_default()
+    };
+    let Some(expected) = (match field("level") {
+        "1" => Some(&fixture.answers[0]),
+        "2" => Some(&fixture.answers[1]),
+        _ => None,
+    }) else {
+        return "You don't seem to be solving the right level.  Did you already complete it?"
+            .to_string();
+    };
+    let answer = field("answer");
+    if answer == expected {
+        if field("level") == "1" {
+            state.lock().unwrap().solved.insert(day);
+        }
+        return "That's the right answer!  You are one gold star closer to restoring snow operations.".to_string();
+    }
+    let hint = match (answer.parse::<i64>(), expected.parse::<i64>()) {
+        (Ok(answer), Ok(expected)) if answer > expected => "; your answer is too high",
+        (Ok(answer), Ok(expected)) if answer < expected => "; your answer is too low",
+        _ => "",
+    };
+    format!("That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data.")
+}
+
+/// The puzzle page, with part 2 once part 1 is solved.
+fn puzzle(day: u8, state: &Mutex<State>) -> String {
+    let mut articles = format!(
+        "<article class=\"day-desc\"><h2>--- Day {day}: Mock ---</h2><p>For example:</p>\n<pre><code>1abc2\n</code></pre>\n</article>\n"
+    );
+    if state.lock().unwrap().solved.contains(&day) {
+        articles.push_str("<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Again.</p>\n</article>\n");
+    }
+    format!("<!DOCTYPE html>\n<html><body><main>\n{articles}</main></body></html>\n")
+}
+
+fn article(text: &str) -> String {
+    format!("<!DOCTYPE html>\n<html><body><main>\n<article><p>{text}</p></article>\n</main></body></html>\n")
+}
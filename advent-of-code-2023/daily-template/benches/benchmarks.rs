@@ -1,22 +1,2 @@
-use {{crate_name}}::*;
-
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
-}
-
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input1.txt",
-    )))
-    .unwrap();
-}
-
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-    .unwrap();
-}
\ No newline at end of file
+// Run with `AOC_BENCH=criterion` or `AOC_BENCH=json` to switch backend.
+aoc_bench::benches!({{crate_name}}::{{project-name | upper_camel_case}});
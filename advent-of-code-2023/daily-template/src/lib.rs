@@ -2,3 +2,44 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use aoc_core::{validate, AllocBudget, InputStats, Part, Solution};
+
+use crate::custom_error::AocError;
+
+pub struct {{project-name | upper_camel_case}};
+
+impl Solution for {{project-name | upper_camel_case}} {
+    const DAY: u8 = {{day}};
+    const NAME: &'static str = "";
+
+    type Parsed<'a> = &'a str;
+    type Error = AocError;
+
+    fn budget(part: Part) -> Option<AllocBudget> {
+        match part {
+            Part::One => Some(part1::BUDGET),
+            Part::Two => Some(part2::BUDGET),
+        }
+    }
+
+    fn validate(_part: Part, input: &str) -> Result<InputStats, AocError> {
+        validate(input)
+    }
+    fn parse(input: &str) -> Result<&str, AocError> {
+        Ok(input)
+    }
+    fn part1(input: &&str) -> Result<String, AocError> {
+        part1::process(input)
+    }
+    fn part2(input: &&str) -> Result<String, AocError> {
+        part2::process(input)
+    }
+}
+
+/// Checks the shape of the input before it is solved.
+pub fn validate(input: &str) -> Result<InputStats, AocError> {
+    Ok(validate::stats(input)?)
+}
@@ -1,10 +1,35 @@
+use std::io::BufRead;
+
+use aoc_core::{AllocBudget, Explanation};
+
 use crate::custom_error::AocError;
 
-#[tracing::instrument]
-pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
-    todo!("day 01 - part 1");
+/// No budget yet, tighten it once the part is solved.
+pub const BUDGET: AllocBudget = AllocBudget {
+    max_bytes: u64::MAX,
+    max_blocks: u64::MAX,
+};
+
+#[tracing::instrument(skip(input), fields(bytes = input.len()))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    todo!("day {{day}} - part 1");
+}
+
+/// Same as [`process`] on the input of `reader`. It starts out reading it all
+/// in; stream it line by line once inputs get too big to hold.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader(mut reader: impl BufRead) -> miette::Result<String, AocError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    process(&input)
+}
+
+/// What each item of the input adds to the answer. It starts out with the
+/// answer alone; add a row per item once the part is solved.
+pub fn explain(input: &str) -> miette::Result<Explanation, AocError> {
+    let mut explanation = Explanation::new(["item", "adds"]);
+    explanation.answer = process(input)?;
+    Ok(explanation)
 }
 
 #[cfg(test)]
@@ -12,9 +37,10 @@ mod tests {
     use super::*;
 
     #[test]
+    #[ignore = "fill in the example answer from the puzzle"]
     fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
+        // Saved by `aoc puzzle <day>`, see `examples/`.
+        let input = include_str!("../examples/part1.txt");
         assert_eq!("", process(input)?);
         Ok(())
     }
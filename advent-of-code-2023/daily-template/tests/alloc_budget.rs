@@ -0,0 +1,12 @@
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+// Runs without the libtest harness: dhat allows one profiler per process, and
+// captured test output would be counted against the budgets.
+fn main() {
+    let _profiler = dhat::Profiler::builder().testing().build();
+    aoc_core::budget::assert_within_budget(
+        &{{crate_name}}::{{project-name | upper_camel_case}},
+        [include_str!("../input1.txt"), include_str!("../input2.txt")],
+    );
+}
@@ -1,22 +1,21 @@
-use day_01::*;
+// Run with `AOC_BENCH=criterion` or `AOC_BENCH=json` to switch backend.
+aoc_bench::benches!(day_01::Day01);
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
-}
+/// The `chars` based part 1 against the byte scanning one.
+mod part1_scan {
+    use divan::{black_box, counter::BytesCount, Bencher};
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input1.txt",
-    )))
-    .unwrap();
-}
+    #[divan::bench]
+    fn chars(bencher: Bencher) {
+        bencher
+            .counter(BytesCount::of_str(super::INPUTS[0]))
+            .bench(|| day_01::part1::process(black_box(super::INPUTS[0])));
+    }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-    .unwrap();
-}
\ No newline at end of file
+    #[divan::bench]
+    fn bytes(bencher: Bencher) {
+        bencher
+            .counter(BytesCount::of_str(super::INPUTS[0]))
+            .bench(|| day_01::part1::process_bytes(black_box(super::INPUTS[0])));
+    }
+}
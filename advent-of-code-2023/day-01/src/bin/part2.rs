@@ -1,5 +1,13 @@
-use day_01::part2::process;
-use miette::Context;
+use aoc_core::{
+    cli::Reporter,
+    report::{sha256, HashingReader},
+    Part, Solution,
+};
+use day_01::{
+    part2::{explain, process, process_reader},
+    Day01,
+};
+use miette::{Context, IntoDiagnostic};
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
@@ -7,14 +15,44 @@ static ALLOC: dhat::Alloc = dhat::Alloc;
 
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
+    let args = aoc_core::cli::args();
+
     #[cfg(feature = "dhat-heap")]
     let _profiler = dhat::Profiler::new_heap();
 
     #[cfg(not(feature = "dhat-heap"))]
-    tracing_subscriber::fmt::init();
+    let _telemetry = aoc_core::telemetry::init(&args);
+
+    if let Some(format) = args.explain {
+        let input =
+            aoc_core::cli::read_input(&args, include_str!("../../input2.txt")).into_diagnostic()?;
+        print!("{}", explain(&aoc_core::normalize(&input))?.render(format));
+        return Ok(());
+    }
 
-    let file = include_str!("../../input2.txt");
-    let result = process(file).context("process part 2")?;
-    println!("{}", result);
+    let mut reporter = Reporter::new(&args, Day01::DAY, Part::Two, Day01::NAME);
+    let result = match &args.input {
+        Some(path) if args.no_validate => {
+            let mut reader = HashingReader::new(aoc_core::cli::open(path).into_diagnostic()?);
+            let result = reporter.time(|| process_reader(&mut reader));
+            reporter.hashed(reader.sha256());
+            result
+        }
+        _ => {
+            let input = aoc_core::cli::read_input(&args, include_str!("../../input2.txt"))
+                .into_diagnostic()?;
+            reporter.hashed(sha256(&input));
+            if !args.no_validate {
+                let stats = Day01::validate(Part::Two, &input)?;
+                tracing::info!(%stats, "input validated");
+                reporter.validated(stats);
+            }
+            let input = aoc_core::normalize(&input);
+            reporter.time(|| process(&input))
+        }
+    }
+    .context("process part 2")?;
+    aoc_core::telemetry::frame_mark();
+    reporter.print(result);
     Ok(())
 }
@@ -1,4 +1,4 @@
-
+use aoc_core::validate::InvalidInput;
 use miette::Diagnostic;
 use thiserror::Error;
 
@@ -7,4 +7,24 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+
+    #[error(transparent)]
+    #[diagnostic(
+        code(aoc::invalid_input),
+        help("check that the input is complete, or pass --no-validate to solve it anyway")
+    )]
+    InvalidInput(#[from] InvalidInput),
+
+    #[error(
+        "no digit on {}: `{content}`",
+        .line.map_or_else(|| "a line".to_string(), |line| format!("line {line}"))
+    )]
+    #[diagnostic(
+        code(aoc::no_digit),
+        help("part 1 only counts written digits, part 2 also spelled out ones")
+    )]
+    NoDigit {
+        line: Option<usize>,
+        content: String,
+    },
+}
@@ -2,3 +2,145 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use aoc_core::{
+    normalize,
+    validate::{self, InvalidInput},
+    AllocBudget, Explanation, InputStats, Part, Solution,
+};
+
+use crate::custom_error::AocError;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const NAME: &'static str = "Trebuchet?!";
+
+    type Parsed<'a> = &'a str;
+    type Error = AocError;
+
+    fn budget(part: Part) -> Option<AllocBudget> {
+        match part {
+            Part::One => Some(part1::BUDGET),
+            Part::Two => Some(part2::BUDGET),
+        }
+    }
+
+    fn validate(part: Part, input: &str) -> Result<InputStats, AocError> {
+        validate(part, input)
+    }
+    fn parse(input: &str) -> Result<&str, AocError> {
+        Ok(input)
+    }
+    fn part1(input: &&str) -> Result<String, AocError> {
+        part1::process(input)
+    }
+    fn part2(input: &&str) -> Result<String, AocError> {
+        part2::process(input)
+    }
+}
+
+/// The first and last digit of a line, which make up its calibration value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    pub first: u32,
+    pub last: u32,
+}
+
+impl Calibration {
+    pub fn value(self) -> u32 {
+        self.first * 10 + self.last
+    }
+}
+
+/// The digits and value of each line of `input`, as found by `calibration`.
+/// Blank lines are skipped, as they are when solving.
+pub fn explain_calibrations(
+    input: &str,
+    calibration: impl Fn(Option<usize>, &str) -> Result<Calibration, AocError>,
+) -> Result<Explanation, AocError> {
+    let mut explanation = Explanation::new(["line", "text", "first", "last", "value"]);
+    let mut sum = 0;
+    for (index, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let found = calibration(Some(index + 1), line)?;
+        sum += found.value();
+        explanation.row([
+            (index + 1).to_string(),
+            line.to_string(),
+            found.first.to_string(),
+            found.last.to_string(),
+            found.value().to_string(),
+        ]);
+    }
+    explanation.answer = sum.to_string();
+    Ok(explanation)
+}
+
+/// Checks that the lines are made of lowercase letters and digits only, each
+/// with a digit that `part` counts: written for part 1, and written or spelled
+/// out for part 2. Blank lines are skipped, as they are when solving.
+pub fn validate(part: Part, input: &str) -> Result<InputStats, AocError> {
+    let stats = validate::stats(input)?;
+    let input = &normalize(input);
+    validate::alphabet(
+        input,
+        |byte| byte.is_ascii_lowercase() || byte.is_ascii_digit(),
+        "a lowercase letter or a digit",
+    )?;
+    for (number, line) in validate::lines(input).filter(|(_, line)| !line.is_empty()) {
+        let line = line.as_bytes();
+        let (found, missing) = match part {
+            Part::One => (line.iter().any(u8::is_ascii_digit), "no digit"),
+            Part::Two => (
+                (0..line.len()).any(|index| part2::digit_at(line, index).is_some()),
+                "no digit, written or spelled out",
+            ),
+        };
+        if !found {
+            return Err(InvalidInput::new(number, missing).into());
+        }
+    }
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_line(result: Result<InputStats, AocError>) -> Option<usize> {
+        match result {
+            Err(AocError::InvalidInput(InvalidInput { line, .. })) => Some(line),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_validate() -> miette::Result<()> {
+        assert_eq!(2, validate(Part::One, "1abc2\r\npqr3stu8vwx\r\n")?.lines);
+        assert_eq!(
+            Some(2),
+            invalid_line(validate(Part::One, "1abc2\nPqr3stu8vwx"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_digits() -> miette::Result<()> {
+        assert_eq!(2, validate(Part::Two, "two1nine\neightwothree\n")?.lines);
+        assert_eq!(
+            Some(2),
+            invalid_line(validate(Part::One, "two1nine\neightwothree\n"))
+        );
+        for part in Part::ALL {
+            assert_eq!(3, validate(part, "1abc2\n\ntreb7uchet\n")?.lines);
+            assert_eq!(Some(1), invalid_line(validate(part, "abc\n1a2\n")));
+        }
+        Ok(())
+    }
+}
@@ -1,22 +1,122 @@
-use crate::custom_error::AocError;
+use std::io::BufRead;
 
-#[tracing::instrument]
+use aoc_core::{reader::for_each_line, AllocBudget, Explanation};
+
+use crate::{custom_error::AocError, explain_calibrations, Calibration};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Only the answer string.
+pub const BUDGET: AllocBudget = AllocBudget {
+    max_bytes: 64,
+    max_blocks: 2,
+};
+
+#[tracing::instrument(skip(input), fields(bytes = input.len()))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
+    #[cfg(not(feature = "parallel"))]
     let output = input
         .lines()
-        .inspect(|line| {
-            dbg!(line);
-        })
-        .map(|line| {
-            let mut num = line.chars().filter_map(|c| c.to_digit(10));
-            let first = num.next().unwrap();
-            let last = if let Some(n) = num.last() { n } else { first };
-            first * 10 + last
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| calibration(Some(index + 1), line).map(Calibration::value))
+        .sum::<Result<u32, _>>()?;
+    #[cfg(feature = "parallel")]
+    let output = input
+        .par_lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| calibration(None, line).map(Calibration::value))
+        .sum::<Result<u32, _>>()?;
+    tracing::debug!(sum = output, "calibration total");
+    Ok(output.to_string())
+}
+
+/// Same as [`process`], reading `reader` one line at a time.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<String, AocError> {
+    let mut output = 0;
+    for_each_line(reader, |number, line| {
+        if line.is_empty() {
+            return Ok(());
+        }
+        output += calibration(Some(number), line)?.value();
+        Ok::<_, AocError>(())
+    })?;
+    tracing::debug!(sum = output, "calibration total");
+    Ok(output.to_string())
+}
+
+/// Byte oriented [`process`]: each line is only scanned in from its start up
+/// to the first digit and from its end back to the last one, never through
+/// the middle. With the `memchr` feature line ends are found with SIMD.
+#[tracing::instrument(skip(input), fields(bytes = input.len()))]
+pub fn process_bytes(input: &str) -> miette::Result<String, AocError> {
+    let input = input.as_bytes();
+    #[cfg(feature = "memchr")]
+    let lines = {
+        let mut start = 0;
+        memchr::memchr_iter(b'\n', input)
+            .chain([input.len()])
+            .map(move |end| {
+                let line = &input[start..end];
+                start = end + 1;
+                line
+            })
+    };
+    #[cfg(not(feature = "memchr"))]
+    let lines = input.split(|&byte| byte == b'\n');
+    let output = lines
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            let no_digit = || AocError::NoDigit {
+                line: Some(index + 1),
+                content: String::from_utf8_lossy(line).into_owned(),
+            };
+            let first = line
+                .iter()
+                .find(|byte| byte.is_ascii_digit())
+                .ok_or_else(no_digit)?;
+            let last = line
+                .iter()
+                .rfind(|byte| byte.is_ascii_digit())
+                .ok_or_else(no_digit)?;
+            Ok(u32::from(first - b'0') * 10 + u32::from(last - b'0'))
         })
-        .sum::<u32>();
+        .sum::<Result<u32, AocError>>()?;
     Ok(output.to_string())
 }
 
+/// The digits of each line and their calibration value, summed as [`process`]
+/// does.
+pub fn explain(input: &str) -> miette::Result<Explanation, AocError> {
+    explain_calibrations(input, calibration)
+}
+
+/// `line` is the 1-based line number, unknown when lines are split across threads.
+fn calibration(line: Option<usize>, content: &str) -> Result<Calibration, AocError> {
+    let mut num = content.chars().filter_map(|c| c.to_digit(10));
+    let first = num.next().ok_or_else(|| AocError::NoDigit {
+        line,
+        content: content.to_string(),
+    })?;
+    let last = if let Some(n) = num.next_back() {
+        n
+    } else {
+        first
+    };
+    let calibration = Calibration { first, last };
+    tracing::trace!(
+        line,
+        first,
+        last,
+        value = calibration.value(),
+        "calibration value"
+    );
+    Ok(calibration)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,6 +128,62 @@ pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet";
         assert_eq!("142", process(input)?);
+        assert_eq!("142", process_reader(input.as_bytes())?);
+        assert_eq!("142", process_bytes(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_digit() {
+        let input = "1abc2\npqr3stu8vwx\nabc\n";
+        assert!(matches!(
+            process(input),
+            Err(AocError::NoDigit { content, .. }) if content == "abc"
+        ));
+        assert!(matches!(
+            process_reader(input.as_bytes()),
+            Err(AocError::NoDigit { line: Some(3), .. })
+        ));
+        assert!(matches!(
+            process_bytes(input),
+            Err(AocError::NoDigit { line: Some(3), .. })
+        ));
+    }
+
+    #[test]
+    fn test_blank_lines() -> miette::Result<()> {
+        let input = "1abc2\r\npqr3stu8vwx\r\n\r\na1b2c3d4e5f\r\ntreb7uchet\r\n";
+        assert_eq!("142", process(input)?);
+        assert_eq!("142", process_reader(input.as_bytes())?);
+        assert_eq!("142", process_bytes(input)?);
+        assert_eq!(4, explain(input)?.rows.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let input = include_str!("../input1.txt");
+        let explanation = explain(input)?;
+        assert_eq!(process(input)?, explanation.answer);
+        assert_eq!(input.lines().count(), explanation.rows.len());
+        let example = explain("pqr3stu8vwx\ntreb7uchet")?;
+        assert_eq!(vec!["2", "treb7uchet", "7", "7", "77"], example.rows[1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_bytes_matches_process() -> miette::Result<()> {
+        let input = include_str!("../input1.txt");
+        assert_eq!(process(input)?, process_bytes(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_bytes_line_endings() -> miette::Result<()> {
+        assert_eq!(
+            "142",
+            process_bytes("1abc2\r\npqr3stu8vwx\r\na1b2c3d4e5f\ntreb7uchet\n")?
+        );
         Ok(())
     }
 }
@@ -1,34 +1,95 @@
-use crate::custom_error::AocError;
-
-#[tracing::instrument]
-pub fn process(
-    input: &str,
-) -> miette::Result<String, AocError> {
-    let output =
-    input
-    .replace("one", "o1e")
-    .replace("two", "t2o")
-    .replace("three", "t3e")
-    .replace("four", "f4r")
-    .replace("five", "f5e")
-    .replace("six", "s6x")
-    .replace("seven", "s7n")
-    .replace("eight", "e8t")
-    .replace("nine", "n9e")
+use std::io::BufRead;
+
+use aoc_core::{reader::for_each_line, AllocBudget, Explanation};
+
+use crate::{custom_error::AocError, explain_calibrations, Calibration};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Only the answer string; spelled digits are matched in place.
+pub const BUDGET: AllocBudget = AllocBudget {
+    max_bytes: 64,
+    max_blocks: 2,
+};
+
+const DIGITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// The digit, written or spelled out, starting at `index`.
+pub fn digit_at(line: &[u8], index: usize) -> Option<u32> {
+    let rest = &line[index..];
+    if rest[0].is_ascii_digit() {
+        return Some(u32::from(rest[0] - b'0'));
+    }
+    DIGITS
+        .iter()
+        .position(|digit| rest.starts_with(digit.as_bytes()))
+        .map(|position| position as u32 + 1)
+}
+
+#[tracing::instrument(skip(input), fields(bytes = input.len()))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    #[cfg(not(feature = "parallel"))]
+    let output = input
         .lines()
-        .inspect(|line| {
-            dbg!(line);
-        })
-        .map(|line| {
-            let mut num = line.chars().filter_map(|c| c.to_digit(10));
-            let first = num.next().unwrap();
-            let last = if let Some(n) = num.last(){
-                n
-            } else { first};
-            first*10+last
-        })
-        .sum::<u32>();
-        Ok(output.to_string())
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| calibration(Some(index + 1), line).map(Calibration::value))
+        .sum::<Result<u32, _>>()?;
+    #[cfg(feature = "parallel")]
+    let output = input
+        .par_lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| calibration(None, line).map(Calibration::value))
+        .sum::<Result<u32, _>>()?;
+    tracing::debug!(sum = output, "calibration total");
+    Ok(output.to_string())
+}
+
+/// Same as [`process`], reading `reader` one line at a time.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<String, AocError> {
+    let mut output = 0;
+    for_each_line(reader, |number, line| {
+        if line.is_empty() {
+            return Ok(());
+        }
+        output += calibration(Some(number), line)?.value();
+        Ok::<_, AocError>(())
+    })?;
+    tracing::debug!(sum = output, "calibration total");
+    Ok(output.to_string())
+}
+
+/// The digits, written or spelled out, of each line and their calibration
+/// value, summed as [`process`] does.
+pub fn explain(input: &str) -> miette::Result<Explanation, AocError> {
+    explain_calibrations(input, calibration)
+}
+
+/// `line` is the 1-based line number, unknown when lines are split across threads.
+fn calibration(line: Option<usize>, content: &str) -> Result<Calibration, AocError> {
+    let bytes = content.as_bytes();
+    let mut num = (0..bytes.len()).filter_map(|index| digit_at(bytes, index));
+    let first = num.next().ok_or_else(|| AocError::NoDigit {
+        line,
+        content: content.to_string(),
+    })?;
+    let last = if let Some(n) = num.next_back() {
+        n
+    } else {
+        first
+    };
+    let calibration = Calibration { first, last };
+    tracing::trace!(
+        line,
+        first,
+        last,
+        value = calibration.value(),
+        "calibration value"
+    );
+    Ok(calibration)
 }
 
 #[cfg(test)]
@@ -45,9 +106,38 @@ xtwone3four
 zoneight234
 7pqrstsixteen";
         assert_eq!("281", process(input)?);
+        assert_eq!("281", process_reader(input.as_bytes())?);
         Ok(())
     }
-}
 
+    #[test]
+    fn test_blank_lines() -> miette::Result<()> {
+        let input = "two1nine\r\n\r\nxtwone3four\r\n";
+        assert_eq!("53", process(input)?);
+        assert_eq!("53", process_reader(input.as_bytes())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let input = include_str!("../input2.txt");
+        assert_eq!(process(input)?, explain(input)?.answer);
+        let example = explain("two1nine\nxtwone3four")?;
+        assert_eq!(vec!["2", "xtwone3four", "2", "4", "24"], example.rows[1]);
+        Ok(())
+    }
 
+    #[test]
+    fn test_no_digit() {
+        assert!(matches!(
+            process_reader("two1nine\nxyz\n".as_bytes()),
+            Err(AocError::NoDigit { line: Some(2), .. })
+        ));
+    }
 
+    #[test]
+    fn test_overlapping_words() -> miette::Result<()> {
+        assert_eq!("82", process("eightwo")?);
+        Ok(())
+    }
+}
@@ -2,104 +2,190 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+use std::io::BufRead;
 
-use std::collections::BTreeMap;
-
+use aoc_core::{
+    normalize,
+    reader::for_each_line,
+    validate::{self, InvalidInput},
+    AllocBudget, InputStats, Part, Solution,
+};
 use nom::{
+    branch::alt,
     bytes::complete::tag,
-    character::complete::{
-        self, alpha1, digit1, line_ending,
-    },
+    character::complete,
+    combinator::value,
     multi::separated_list1,
     sequence::{preceded, separated_pair},
-    IResult,
+    Finish, IResult, Parser,
 };
 
 use crate::custom_error::AocError;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const NAME: &'static str = "Cube Conundrum";
+
+    type Parsed<'a> = Vec<Game>;
+    type Error = AocError;
+
+    fn budget(part: Part) -> Option<AllocBudget> {
+        match part {
+            Part::One => Some(part1::BUDGET),
+            Part::Two => Some(part2::BUDGET),
+        }
+    }
+
+    fn validate(_part: Part, input: &str) -> Result<InputStats, AocError> {
+        validate(input)
+    }
+    fn parse(input: &str) -> Result<Vec<Game>, AocError> {
+        parse(input)
+    }
+    fn part1(games: &Vec<Game>) -> Result<String, AocError> {
+        part1::solve(games)
+    }
+    fn part2(games: &Vec<Game>) -> Result<String, AocError> {
+        part2::solve(games)
+    }
+}
 
-#[derive(Debug)]
-struct Cube<'a> {
-    color: &'a str,
-    amount: u32,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
 }
 
-#[derive(Debug)]
-struct Game<'a> {
-    id: &'a str,
-    rounds: Vec<Vec<Cube<'a>>>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cube {
+    pub color: Color,
+    pub amount: u32,
 }
 
-impl<'a> Game<'a> {
-    fn valid_for_cube_set(
-        &self,
-        map: &BTreeMap<&str, u32>,
-    ) -> Option<u32> {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Vec<Cube>>,
+}
+
+impl Game {
+    /// The most cubes of `color` revealed at once during the game.
+    pub fn max(&self, color: Color) -> u32 {
         self.rounds
             .iter()
-            .all(|round| {
-                round.iter().all(|shown_cube| {
-                    shown_cube.amount
-                        <= *map
-                            .get(shown_cube.color)
-                            .expect("a valid cube")
-                })
-            })
-            .then_some(
-                self.id.parse::<u32>().expect(
-                    "game id should a parsable u32",
-                ),
-            )
+            .flatten()
+            .filter(|cube| cube.color == color)
+            .map(|cube| cube.amount)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Checks that every line is a game, with ids increasing from one line to the
+/// next.
+pub fn validate(input: &str) -> Result<InputStats, AocError> {
+    let stats = validate::stats(input)?;
+    let input = &normalize(input);
+    validate::alphabet(
+        input,
+        |byte| byte.is_ascii_alphanumeric() || b" :;,".contains(&byte),
+        "a letter, a digit, a space or `:;,`",
+    )?;
+    let mut previous = 0;
+    for (number, line) in validate::lines(input) {
+        let id = line
+            .strip_prefix("Game ")
+            .and_then(|line| line.split_once(':'))
+            .and_then(|(id, _)| id.parse::<u32>().ok())
+            .ok_or_else(|| InvalidInput::new(number, "expected `Game <id>:`"))?;
+        if id <= previous {
+            Err(InvalidInput::new(
+                number,
+                format!("game {id} comes after game {previous}"),
+            ))?;
+        }
+        previous = id;
     }
+    Ok(stats)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &str) -> Result<Vec<Game>, AocError> {
+    #[cfg(not(feature = "parallel"))]
+    let lines = input.lines();
+    #[cfg(feature = "parallel")]
+    let lines = input.par_lines();
+    let games = lines
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_game(line, || line_number(input, line)))
+        .collect::<Result<_, _>>()?;
+    Ok(games)
+}
+
+/// Parses the games of `reader` one line at a time, handing each to `f`
+/// instead of keeping them all.
+pub fn for_each_game(reader: impl BufRead, mut f: impl FnMut(Game)) -> Result<(), AocError> {
+    for_each_line(reader, |number, line| {
+        let line = line.trim();
+        if !line.is_empty() {
+            f(parse_game(line, || number)?);
+        }
+        Ok(())
+    })
+}
+
+/// `line_number` is only worked out when the line does not parse.
+fn parse_game(line: &str, line_number: impl FnOnce() -> usize) -> Result<Game, AocError> {
+    game(line)
+        .finish()
+        .map(|(_, game)| game)
+        .map_err(|_| AocError::ParseError {
+            line: line_number(),
+            content: line.to_string(),
+        })
 }
 
+/// 1-based number of the line of `input` that `line` was sliced from.
+fn line_number(input: &str, line: &str) -> usize {
+    let offset = line.as_ptr() as usize - input.as_ptr() as usize;
+    input.as_bytes()[..offset]
+        .iter()
+        .filter(|&&byte| byte == b'\n')
+        .count()
+        + 1
+}
+
+fn color(input: &str) -> IResult<&str, Color> {
+    alt((
+        value(Color::Red, tag("red")),
+        value(Color::Green, tag("green")),
+        value(Color::Blue, tag("blue")),
+    ))(input)
+}
 // 4 red
 fn cube(input: &str) -> IResult<&str, Cube> {
-    let (input, (amount, color)) =
-        separated_pair(complete::u32, tag(" "), alpha1)(
-            input,
-        )?;
-    Ok((input, Cube { color, amount }))
+    separated_pair(complete::u32, tag(" "), color)
+        .map(|(amount, color)| Cube { color, amount })
+        .parse(input)
 }
 // 3 blue, 4 red
 fn round(input: &str) -> IResult<&str, Vec<Cube>> {
-    let (input, cubes) =
-        separated_list1(tag(", "), cube)(input)?;
-    Ok((input, cubes))
+    separated_list1(tag(", "), cube)(input)
 }
 // Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 fn game(input: &str) -> IResult<&str, Game> {
-    let (input, id) =
-        preceded(tag("Game "), digit1)(input)?;
-    let (input, rounds) = preceded(
-        tag(": "),
-        separated_list1(tag("; "), round),
-    )(input)?;
-    Ok((input, Game { rounds, id }))
-}
-fn parse_games(input: &str) -> IResult<&str, Vec<Game>> {
-    let (input, games) =
-        separated_list1(line_ending, game)(input)?;
-    Ok((input, games))
-}
-
-#[tracing::instrument]
-pub fn process(
-    input: &str,
-) -> miette::Result<String, AocError> {
-    let map = BTreeMap::from([
-        ("red", 12),
-        ("green", 13),
-        ("blue", 14),
-    ]);
-    let games = parse_games(input).expect("should parse");
-
-    Ok(games
-        .1
-        .iter()
-        .filter_map(|game| game.valid_for_cube_set(&map))
-        .sum::<u32>()
-        .to_string())
+    let (input, id) = preceded(tag("Game "), complete::u32)(input)?;
+    let (input, rounds) = preceded(tag(": "), separated_list1(tag("; "), round))(input)?;
+    Ok((input, Game { id, rounds }))
 }
 
 #[cfg(test)]
@@ -107,13 +193,77 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() -> miette::Result<()> {
+    fn test_cube() {
+        assert_eq!(
+            Ok((
+                "",
+                Cube {
+                    color: Color::Blue,
+                    amount: 3
+                }
+            )),
+            cube("3 blue")
+        );
+    }
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
         let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!("8", process(input)?);
+        let games = parse(input)?;
+        assert_eq!(5, games.len());
+        assert_eq!(3, games[2].rounds.len());
+        assert_eq!(20, games[2].max(Color::Red));
+        assert_eq!(4, games[1].max(Color::Blue));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> miette::Result<()> {
+        assert_eq!(
+            2,
+            validate("Game 1: 3 blue\nGame 3: 2 red, 1 green\n")?.lines
+        );
+        assert!(matches!(
+            validate("Game 1: 3 blue\nGame 1: 2 red\n"),
+            Err(AocError::InvalidInput(InvalidInput { line: 2, .. }))
+        ));
+        assert!(matches!(
+            validate("Game 1: 3 blue\n3 red\n"),
+            Err(AocError::InvalidInput(InvalidInput { line: 2, .. }))
+        ));
+        assert!(matches!(
+            validate("Game 1: 3 blue!\n"),
+            Err(AocError::InvalidInput(InvalidInput { line: 1, .. }))
+        ));
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            parse("Game 1: 3 blue\nGame 2: 3 purple"),
+            Err(AocError::ParseError { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_after_blank_line() {
+        assert!(matches!(
+            parse("Game 1: 3 blue\n\n  Game 3: 3 purple"),
+            Err(AocError::ParseError { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_for_each_game_error() {
+        let reader = "Game 1: 3 blue\n\nGame 3: 3 purple".as_bytes();
+        assert!(matches!(
+            for_each_game(reader, |_| ()),
+            Err(AocError::ParseError { line: 3, .. })
+        ));
+    }
 }
@@ -1,133 +1,92 @@
-use nom::{
-    bytes::complete::{tag, take_while1},
-    character::complete::digit1,
-    combinator::map_res,
-    multi::separated_list1,
-    sequence::preceded,
-    Finish, IResult,
-};
+use std::io::BufRead;
+
+use aoc_core::{AllocBudget, Explanation};
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, for_each_game, parse, Color, Game};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// The parsed games plus the answer string.
+pub const BUDGET: AllocBudget = AllocBudget {
+    max_bytes: 48 * 1024,
+    max_blocks: 700,
+};
 
 const MAX_RED: u32 = 12;
 const MAX_GREEN: u32 = 13;
 const MAX_BLUE: u32 = 14;
 
-struct Game {
-    rounds: Vec<Round>,
-}
-
-impl Game {
-    fn from(rounds: Vec<Round>) -> Self {
-        Self { rounds }
-    }
-    fn is_possible(&self) -> bool {
-        self.rounds.iter().all(|round| round.is_possible())
-    }
-}
-
-struct Round {
-    cubes: Vec<Cube>,
-}
-
-impl Round {
-    fn from(cubes: Vec<Cube>) -> Self {
-        Self { cubes }
-    }
-    fn is_possible(&self) -> bool {
-        self.cubes.iter().all(|cube| cube.le_max_cube())
-    }
+/// Whether the bag could hold every cube revealed during `game`.
+pub fn is_possible(game: &Game) -> bool {
+    game.max(Color::Red) <= MAX_RED
+        && game.max(Color::Green) <= MAX_GREEN
+        && game.max(Color::Blue) <= MAX_BLUE
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
-enum Cube {
-    Red(u32),
-    Green(u32),
-    Blue(u32),
-}
-impl Cube {
-    fn from(str: &str) -> Self {
-        let input: Vec<&str> = str.trim().split(' ').collect();
-        let (revealed, color) = (input[0], input[1]);
-        match color {
-            "red" => Cube::Red(revealed.parse::<u32>().unwrap()),
-            "green" => Cube::Green(revealed.parse::<u32>().unwrap()),
-            "blue" => Cube::Blue(revealed.parse::<u32>().unwrap()),
-            _ => panic!(""),
-        }
-    }
-    fn le_max_cube(&self) -> bool {
-        match self {
-            Cube::Red(revealed) => *revealed <= MAX_RED,
-            Cube::Green(revealed) => *revealed <= MAX_GREEN,
-            Cube::Blue(revealed) => *revealed <= MAX_BLUE,
-        }
+/// What `game` adds to the answer: its id if it was possible.
+fn score(game: &Game) -> u32 {
+    if is_possible(game) {
+        game.id
+    } else {
+        0
     }
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(bytes = input.len()))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let output = input
-        .lines()
-        .filter_map(|line| process_line(line.trim()).finish().ok())
-        .map(|output| output.1)
-        .sum::<u32>();
-    Ok(output.to_string())
+    solve(&parse(input)?)
 }
 
-fn process_line(line: &str) -> IResult<&str, u32> {
-    let mut parser = preceded(tag("Game "), map_res(digit1, str::parse::<u32>));
-    let (game_input, id) = parser(line)?;
-    let game = game(game_input);
-    game.and_then(|(_, game)| {
-        if game.is_possible() {
-            Ok((game_input, id))
-        } else {
-            Err(nom::Err::Error(nom::error::Error::new(
-                game_input,
-                nom::error::ErrorKind::Verify,
-            )))
-        }
-    })
+/// Same as [`process`], reading `reader` one game at a time.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<String, AocError> {
+    let mut output = 0;
+    for_each_game(reader, |game| output += score(&game))?;
+    Ok(output.to_string())
 }
 
-fn game(input: &str) -> IResult<&str, Game> {
-    let mut parser = preceded(
-        tag(": "),
-        separated_list1(tag(";"), take_while1(|c| c != ';')),
-    );
-    let rounds_input = parser(input)?.1;
-    let rounds = rounds_input
-        .into_iter()
-        .map(round)
-        .filter_map(|res| res.ok())
-        .map(|res| res.1)
-        .collect::<Vec<Round>>();
-    Ok((input, Game::from(rounds)))
+#[tracing::instrument(skip(games))]
+pub fn solve(games: &[Game]) -> miette::Result<String, AocError> {
+    let output = tracing::info_span!("sum").in_scope(|| {
+        #[cfg(not(feature = "parallel"))]
+        let games = games.iter();
+        #[cfg(feature = "parallel")]
+        let games = games.par_iter();
+        games.map(score).sum::<u32>()
+    });
+    Ok(output.to_string())
 }
 
-fn round(input: &str) -> IResult<&str, Round> {
-    let mut parser = preceded(
-        tag(""),
-        separated_list1(tag(", "), take_while1(|c| c != ',')),
-    );
-    let cubes_input = parser(input)?.1;
-    let cubes = cubes_input
-        .into_iter()
-        .map(Cube::from)
-        .collect::<Vec<Cube>>();
-    Ok((input, Round::from(cubes)))
+/// The most cubes of each color shown in every game and whether the game was
+/// possible, summed as [`process`] does.
+pub fn explain(input: &str) -> miette::Result<Explanation, AocError> {
+    let mut explanation = Explanation::new(["game", "red", "green", "blue", "verdict", "adds"]);
+    let mut sum = 0;
+    for game in parse(input)? {
+        let adds = score(&game);
+        sum += adds;
+        explanation.row([
+            game.id.to_string(),
+            game.max(Color::Red).to_string(),
+            game.max(Color::Green).to_string(),
+            game.max(Color::Blue).to_string(),
+            if is_possible(&game) {
+                "possible"
+            } else {
+                "impossible"
+            }
+            .to_string(),
+            adds.to_string(),
+        ]);
+    }
+    explanation.answer = sum.to_string();
+    Ok(explanation)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_cube_from() {
-        let input = "3 blue";
-        assert_eq!(Cube::from(input), Cube::Blue(3));
-    }
     #[test]
     fn test_process() -> miette::Result<()> {
         let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
@@ -136,6 +95,19 @@ mod tests {
         Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
         Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
         assert_eq!("8", process(input)?);
+        assert_eq!("8", process_reader(input.as_bytes())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let input = include_str!("../input1.txt");
+        assert_eq!(process(input)?, explain(input)?.answer);
+        let example = explain("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green")?;
+        assert_eq!(
+            vec!["3", "20", "13", "6", "impossible", "0"],
+            example.rows[0]
+        );
         Ok(())
     }
 }
@@ -1,138 +1,64 @@
-use nom::{
-    bytes::complete::{tag, take_while1},
-    character::complete::digit1,
-    combinator::map_res,
-    multi::separated_list1,
-    sequence::preceded,
-    Finish, IResult,
-};
-
-use crate::custom_error::AocError;
+use std::io::BufRead;
 
-struct Game {
-    rounds: Vec<Round>,
-}
+use aoc_core::{AllocBudget, Explanation};
 
-impl Game {
-    
-    fn from(rounds: Vec<Round>) -> Self {
-        Self { rounds }
-    }
+use crate::{custom_error::AocError, for_each_game, parse, Color, Game};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-    fn min_cubes(&self, color: &str) -> u32 {
-        self.rounds
-            .iter()
-            .map(|round| round.revealed(color))
-            .reduce(|min_cubes, cubes_round| {
-                if min_cubes > cubes_round {
-                    min_cubes
-                } else {
-                    cubes_round
-                }
-            })
-            .unwrap()
-    }
-
-    fn power(&self) -> u32 {
-        let min_red = self.min_cubes("red");
-        let min_green = self.min_cubes("green");
-        let min_blue = self.min_cubes("blue");
-        min_red * min_green * min_blue
-    }
-}
+/// The parsed games plus the answer string.
+pub const BUDGET: AllocBudget = AllocBudget {
+    max_bytes: 48 * 1024,
+    max_blocks: 700,
+};
 
-struct Round {
-    cubes: Vec<Cube>,
+/// Product of the fewest cubes of each color the game could be played with.
+pub fn power(game: &Game) -> u32 {
+    game.max(Color::Red) * game.max(Color::Green) * game.max(Color::Blue)
 }
 
-impl Round {
-    fn from(cubes: Vec<Cube>) -> Self {
-        Self { cubes }
-    }
-
-    fn revealed(&self, color: &str) -> u32 {
-        self.cubes
-            .iter()
-            .filter_map(|cube| match (cube, color) {
-                (Cube::Red(revealed), "red")
-                | (Cube::Green(revealed), "green")
-                | (Cube::Blue(revealed), "blue") => Some(*revealed),
-                _ => None,
-            })
-            .last()
-            .unwrap_or_default()
-    }
+#[tracing::instrument(skip(input), fields(bytes = input.len()))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
-enum Cube {
-    Red(u32),
-    Green(u32),
-    Blue(u32),
-}
-impl Cube {
-    fn from(str: &str) -> Self {
-        let input: Vec<&str> = str.trim().split(' ').collect();
-        let (revealed, color) = (input[0], input[1]);
-        match color {
-            "red" => Cube::Red(revealed.parse::<u32>().unwrap()),
-            "green" => Cube::Green(revealed.parse::<u32>().unwrap()),
-            "blue" => Cube::Blue(revealed.parse::<u32>().unwrap()),
-            _ => panic!("wrong input"),
-        }
-    }
+/// Same as [`process`], reading `reader` one game at a time.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<String, AocError> {
+    let mut output = 0;
+    for_each_game(reader, |game| output += power(&game))?;
+    Ok(output.to_string())
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let output = input
-        .lines()
-        .filter_map(|line| process_line(line.trim()).finish().ok())
-        .map(|output| output.1)
-        .sum::<u32>();
+#[tracing::instrument(skip(games))]
+pub fn solve(games: &[Game]) -> miette::Result<String, AocError> {
+    let output = tracing::info_span!("sum").in_scope(|| {
+        #[cfg(not(feature = "parallel"))]
+        let games = games.iter();
+        #[cfg(feature = "parallel")]
+        let games = games.par_iter();
+        games.map(power).sum::<u32>()
+    });
     Ok(output.to_string())
 }
 
-fn process_line(line: &str) -> IResult<&str, u32> {
-    let mut parser = preceded(tag("Game "), map_res(digit1, str::parse::<u32>));
-    let (game_input, _) = parser(line)?;
-    let game = game(game_input);
-    if let Ok((_, game)) = game {
-        Ok((game_input, game.power()))
-    } else {
-        Err(nom::Err::Error(nom::error::Error::new(
-            game_input,
-            nom::error::ErrorKind::Verify,
-        )))
+/// The fewest cubes of each color every game could be played with and their
+/// power, summed as [`process`] does.
+pub fn explain(input: &str) -> miette::Result<Explanation, AocError> {
+    let mut explanation = Explanation::new(["game", "red", "green", "blue", "power"]);
+    let mut sum = 0;
+    for game in parse(input)? {
+        sum += power(&game);
+        explanation.row([
+            game.id.to_string(),
+            game.max(Color::Red).to_string(),
+            game.max(Color::Green).to_string(),
+            game.max(Color::Blue).to_string(),
+            power(&game).to_string(),
+        ]);
     }
-}
-
-fn game(input: &str) -> IResult<&str, Game> {
-    let mut parser = preceded(
-        tag(": "),
-        separated_list1(tag(";"), take_while1(|c| c != ';')),
-    );
-    let rounds_input = parser(input)?.1;
-    let rounds = rounds_input
-        .into_iter()
-        .map(round)
-        .filter_map(|res| res.ok())
-        .map(|res| res.1)
-        .collect::<Vec<Round>>();
-    Ok((input, Game::from(rounds)))
-}
-
-fn round(input: &str) -> IResult<&str, Round> {
-    let mut parser = preceded(
-        tag(""),
-        separated_list1(tag(", "), take_while1(|c| c != ',')),
-    );
-    let cubes_input = parser(input)?.1;
-    let cubes = cubes_input
-        .into_iter()
-        .map(Cube::from)
-        .collect::<Vec<Cube>>();
-    Ok((input, Round::from(cubes)))
+    explanation.answer = sum.to_string();
+    Ok(explanation)
 }
 
 #[cfg(test)]
@@ -146,6 +72,16 @@ mod tests {
         Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
         Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
         assert_eq!("2286", process(input)?);
+        assert_eq!("2286", process_reader(input.as_bytes())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let input = include_str!("../input2.txt");
+        assert_eq!(process(input)?, explain(input)?.answer);
+        let example = explain("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")?;
+        assert_eq!(vec!["1", "4", "2", "6", "48"], example.rows[0]);
         Ok(())
     }
 }
@@ -0,0 +1,12 @@
+use aoc_core::normalize;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(js_name = day02_part1)]
+pub fn part1(input: &str) -> Result<String, JsError> {
+    crate::part1::process(&normalize(input)).map_err(|error| JsError::new(&error.to_string()))
+}
+
+#[wasm_bindgen(js_name = day02_part2)]
+pub fn part2(input: &str) -> Result<String, JsError> {
+    crate::part2::process(&normalize(input)).map_err(|error| JsError::new(&error.to_string()))
+}
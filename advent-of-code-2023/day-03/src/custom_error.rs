@@ -1,3 +1,4 @@
+use aoc_core::validate::InvalidInput;
 use miette::Diagnostic;
 use thiserror::Error;
 
@@ -6,4 +7,15 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(
+        code(aoc::invalid_input),
+        help("check that the input is complete, or pass --no-validate to solve it anyway")
+    )]
+    InvalidInput(#[from] InvalidInput),
+
+    #[error("could not parse schematic line {line}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError { line: usize },
 }
@@ -1,4 +1,76 @@
 pub mod custom_error;
+pub mod schematic;
 
 pub mod part1;
 pub mod part2;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use aoc_core::{normalize, validate, AllocBudget, InputStats, Part, Solution};
+
+use crate::{custom_error::AocError, schematic::Schematic};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const NAME: &'static str = "Gear Ratios";
+
+    type Parsed<'a> = Schematic<'a>;
+    type Error = AocError;
+
+    fn budget(part: Part) -> Option<AllocBudget> {
+        match part {
+            Part::One => Some(part1::BUDGET),
+            Part::Two => Some(part2::BUDGET),
+        }
+    }
+
+    fn validate(_part: Part, input: &str) -> Result<InputStats, AocError> {
+        validate(input)
+    }
+    fn parse(input: &str) -> Result<Schematic<'_>, AocError> {
+        schematic::parse(input)
+    }
+    fn part1(schematic: &Schematic) -> Result<String, AocError> {
+        part1::solve(schematic)
+    }
+    fn part2(schematic: &Schematic) -> Result<String, AocError> {
+        part2::solve(schematic)
+    }
+}
+
+/// Checks that the schematic is a rectangle of printable characters.
+pub fn validate(input: &str) -> Result<InputStats, AocError> {
+    let stats = validate::stats(input)?;
+    let input = &normalize(input);
+    validate::rectangular(input)?;
+    validate::alphabet(
+        input,
+        |byte| byte.is_ascii_graphic(),
+        "a digit, `.` or a symbol",
+    )?;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::validate::InvalidInput;
+
+    use super::*;
+
+    #[test]
+    fn test_validate() -> miette::Result<()> {
+        let stats = validate("467..114\n...*....\n..35..63\n")?;
+        assert_eq!((3, 8), (stats.lines, stats.width));
+        assert!(matches!(
+            validate("467..114\n...*...\n"),
+            Err(AocError::InvalidInput(InvalidInput { line: 2, .. }))
+        ));
+        assert!(matches!(
+            validate("467..114\n... ....\n"),
+            Err(AocError::InvalidInput(InvalidInput { line: 2, .. }))
+        ));
+        Ok(())
+    }
+}
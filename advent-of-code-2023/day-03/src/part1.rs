@@ -1,219 +1,150 @@
-use std::{collections::BTreeMap, error::Error};
+use std::io::BufRead;
 
-use nom::{
-    branch::alt,
-    bytes::complete::{is_not, take_till1},
-    character::complete::digit1,
-    multi::many1,
-    IResult, Parser,
+use aoc_core::{AllocBudget, Explanation};
+
+use crate::{
+    custom_error::AocError,
+    schematic::{for_each_window, parse, Number, Schematic, Symbol},
 };
-use nom_locate::LocatedSpan;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-use crate::custom_error::AocError;
-type Span<'a> = LocatedSpan<&'a str>;
+/// The rows of the schematic and the answer string.
+pub const BUDGET: AllocBudget = AllocBudget {
+    max_bytes: 4 * 1024,
+    max_blocks: 2,
+};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Number {
-    value: usize,
-    x: Vec<usize>,
-    y: Option<usize>,
+#[tracing::instrument(skip(input), fields(bytes = input.len()))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
 }
 
-impl Number {
-    fn from(span: Span) -> Self {
-        let value = span.fragment();
-        let offset = span.location_offset();
-        let x = (offset.saturating_sub(1)..offset + value.len() + 1).collect();
-        Number {
-            value: value.parse().expect("shloud be a number"),
-            x,
-            y: None,
-        }
-    }
-    fn with(mut self, y: usize) -> Self {
-        self.y = Some(y);
-        self
-    }
+/// Same as [`process`], reading `reader` one row at a time.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<String, AocError> {
+    let mut sum = 0;
+    for_each_window(reader, |window| {
+        sum += validate_parts_in(window, 1)
+            .map(|part| part.value())
+            .sum::<usize>();
+    })?;
+    Ok(sum.to_string())
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Symbol {
-    x: usize,
-}
-impl Symbol {
-    fn from(span: Span) -> Self {
-        Symbol {
-            x: span.location_offset(),
-        }
-    }
+#[tracing::instrument(skip(schematic))]
+pub fn solve(schematic: &Schematic) -> miette::Result<String, AocError> {
+    #[cfg(not(feature = "parallel"))]
+    let rows = 0..schematic.height();
+    #[cfg(feature = "parallel")]
+    let rows = (0..schematic.height()).into_par_iter();
+    // Each row is validated in a span of its own, so that Tracy shows the
+    // validation apart from the sum around it.
+    let sum = tracing::info_span!("sum").in_scope(|| {
+        rows.map(|y| {
+            tracing::info_span!("validate", y).in_scope(|| {
+                validate_parts_in(schematic, y)
+                    .map(|part| part.value())
+                    .sum::<usize>()
+            })
+        })
+        .sum::<usize>()
+    });
+    Ok(sum.to_string())
 }
 
-#[derive(Clone, PartialEq, Debug, Eq)]
-enum Value {
-    Number(Number),
-    Symbol(Symbol),
-    Empty,
+/// Numbers of row `y` next to a symbol.
+pub fn validate_parts_in<'s, 'a>(
+    schematic: &'s Schematic<'a>,
+    y: usize,
+) -> impl Iterator<Item = Number<'a>> + 's {
+    numbers_and_symbols_in(schematic, y).filter_map(|(number, symbol)| symbol.map(|_| number))
 }
 
-#[tracing::instrument]
-pub fn process<'a>(input: &'a str) -> miette::Result<String, AocError> {
-    let (numbers, symbols) = parse(input).unwrap();
-    let valid_parts = validate_parts(numbers, symbols);
-    Ok(valid_parts
-        .iter()
-        .map(|part| part.value)
-        .sum::<usize>()
-        .to_string())
+/// Numbers of row `y`, each with the first symbol next to it if any.
+fn numbers_and_symbols_in<'s, 'a>(
+    schematic: &'s Schematic<'a>,
+    y: usize,
+) -> impl Iterator<Item = (Number<'a>, Option<Symbol>)> + 's {
+    schematic
+        .numbers_in(y)
+        .map(|number| (number, schematic.symbols_around(&number).next()))
 }
 
-fn parse<'a>(
-    input: &'a str,
-) -> Result<(Vec<Number>, BTreeMap<usize, Vec<usize>>), Box<dyn Error + 'a>> {
-    let mut numbers = vec![];
-    let mut symbols = BTreeMap::new();
-    for (y, line) in input.lines().enumerate() {
-        let values = parse_line(line)?.1;
-        for value in values {
-            match value {
-                Value::Number(number) => numbers.push(number.with(y)),
-                Value::Symbol(symbol) => {
-                    symbols
-                        .entry(y)
-                        .and_modify(|e: &mut Vec<usize>| e.push(symbol.x))
-                        .or_insert(vec![symbol.x]);
-                }
-                Value::Empty => (),
-            }
+/// Every number with a symbol next to it, if any, summed as [`process`] does.
+pub fn explain(input: &str) -> miette::Result<Explanation, AocError> {
+    let schematic = parse(input)?;
+    let mut explanation = Explanation::new(["line", "column", "number", "symbol", "adds"]);
+    let mut sum = 0;
+    for y in 0..schematic.height() {
+        for (number, symbol) in numbers_and_symbols_in(&schematic, y) {
+            let adds = symbol.map_or(0, |_| number.value());
+            sum += adds;
+            explanation.row([
+                (number.y + 1).to_string(),
+                (number.x + 1).to_string(),
+                number.value().to_string(),
+                symbol.map_or("none".to_string(), |symbol| {
+                    let kind = char::from(symbol.kind);
+                    format!("{kind} at {}:{}", symbol.y + 1, symbol.x + 1)
+                }),
+                adds.to_string(),
+            ]);
         }
     }
-
-    Ok((numbers, symbols))
-}
-
-fn parse_line(input: &str) -> IResult<Span, Vec<Value>> {
-    let input = Span::new(input.trim());
-    many1(alt((
-        is_not(".0123456789").map(Symbol::from).map(Value::Symbol),
-        digit1.map(Number::from).map(Value::Number),
-        take_till1(|c: char| c.is_ascii_digit() || c != '.').map(|_| Value::Empty),
-    )))(input)
-}
-
-fn validate_parts(numbers: Vec<Number>, symbols: BTreeMap<usize, Vec<usize>>) -> Vec<Number> {
-    numbers
-        .into_iter()
-        .filter(|number| {
-            let y = number.y.unwrap();
-            let mut lines_to_check = vec![y.saturating_sub(1), y, y + 1];
-            lines_to_check.dedup();
-            lines_to_check.iter().any(|y| {
-                if let Some(positions) = symbols.get(y) {
-                    positions.iter().any(|pos| number.x.contains(pos))
-                } else {
-                    false
-                }
-            })
-        })
-        .collect()
+    explanation.answer = sum.to_string();
+    Ok(explanation)
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_core::input::dedent;
+
     use super::*;
 
     #[test]
-    fn test_process_line() {
-        let input = "300.400..500%..";
+    fn test_validate_numbers() -> miette::Result<()> {
+        let input = dedent(
+            "..123%..22..*
+            /.32.....$.09",
+        );
+        let schematic = parse(&input)?;
         assert_eq!(
-            vec![
-                Value::Number(Number {
-                    value: 300,
-                    x: vec![0, 1, 2],
-                    y: None
-                }),
-                Value::Empty,
-                Value::Number(Number {
-                    value: 400,
-                    x: vec![4, 5, 6],
-                    y: None
-                }),
-                Value::Empty,
-                Value::Number(Number {
-                    value: 500,
-                    x: vec![9, 10, 11],
-                    y: None
-                }),
-                Value::Symbol(Symbol { x: 12 }),
-                Value::Empty
-            ],
-            parse_line(input).unwrap().1
-        )
+            vec![123, 22, 9],
+            (0..schematic.height())
+                .flat_map(|y| validate_parts_in(&schematic, y))
+                .map(|number| number.value())
+                .collect::<Vec<_>>()
+        );
+        Ok(())
     }
 
     #[test]
-    fn test_validate_numbers() {
-        let _input = "..123%..22..*
-                            /.32.....$.09";
-        let mut symbols = BTreeMap::new();
-        symbols.insert(0, vec![5, 12]);
-        symbols.insert(1, vec![0, 9]);
-        let numbers = vec![
-            Number {
-                value: 123,
-                x: vec![2, 3, 4],
-                y: Some(0),
-            },
-            Number {
-                value: 22,
-                x: vec![8, 9, 10],
-                y: Some(0),
-            },
-            Number {
-                value: 32,
-                x: vec![11, 12],
-                y: Some(1),
-            },
-            Number {
-                value: 09,
-                x: vec![11, 12],
-                y: Some(1),
-            },
-        ];
-        assert_eq!(
-            validate_parts(numbers, symbols),
-            vec![
-                Number {
-                    value: 123,
-                    x: vec![2, 3, 4],
-                    y: Some(0)
-                },
-                Number {
-                    value: 22,
-                    x: vec![8, 9, 10],
-                    y: Some(0)
-                },
-                Number {
-                    value: 09,
-                    x: vec![11, 12],
-                    y: Some(1)
-                }
-            ]
+    fn test_process() -> miette::Result<()> {
+        let input = dedent(
+            "467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..",
         );
+        assert_eq!("4361", process(&input)?);
+        assert_eq!("4361", process_reader(input.as_bytes())?);
+        Ok(())
     }
 
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "467..114..
-        ...*......
-        ..35..633.
-        ......#...
-        617*......
-        .....+.58.
-        ..592.....
-        ......755.
-        ...$.*....
-        .664.598..";
-        assert_eq!("4361", process(input)?);
+    fn test_explain() -> miette::Result<()> {
+        let input = include_str!("../input1.txt");
+        assert_eq!(process(input)?, explain(input)?.answer);
+        let example = explain("467..114..\n...*......")?;
+        assert_eq!(vec!["1", "1", "467", "* at 2:4", "467"], example.rows[0]);
+        assert_eq!(vec!["1", "6", "114", "none", "0"], example.rows[1]);
         Ok(())
     }
 }
@@ -1,147 +1,123 @@
-use std::{collections::BTreeMap, error::Error};
+use std::io::BufRead;
 
-use nom::{
-    branch::alt,
-    bytes::complete::{is_a, take_till1},
-    character::complete::digit1,
-    multi::many1,
-    IResult, Parser,
-};
-use nom_locate::LocatedSpan;
-
-use crate::custom_error::AocError;
-type Span<'a> = LocatedSpan<&'a str>;
+use aoc_core::{AllocBudget, Explanation};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Number {
-    value: usize,
-    x: Vec<usize>,
-    y: Option<usize>,
-}
+use crate::{
+    custom_error::AocError,
+    schematic::{for_each_window, parse, Schematic, Symbol},
+};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-impl Number {
-    fn from(span: Span) -> Self {
-        let value = span.fragment();
-        let offset = span.location_offset();
-        let x = (offset.saturating_sub(1)..offset + value.len() + 1).collect();
-        Number {
-            value: value.parse().expect("shloud be a number"),
-            x,
-            y: None,
-        }
-    }
-    fn with(mut self, y: usize) -> Self {
-        self.y = Some(y);
-        self
-    }
-}
+/// The rows of the schematic and the answer string.
+pub const BUDGET: AllocBudget = AllocBudget {
+    max_bytes: 4 * 1024,
+    max_blocks: 2,
+};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Symbol {
-    x: usize,
-}
-impl Symbol {
-    fn from(span: Span) -> Self {
-        Symbol {
-            x: span.location_offset(),
-        }
-    }
+#[tracing::instrument(skip(input), fields(bytes = input.len()))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
 }
 
-#[derive(Clone, PartialEq, Debug, Eq)]
-enum Value {
-    Number(Number),
-    Symbol(Symbol),
-    Empty,
+/// Same as [`process`], reading `reader` one row at a time.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<String, AocError> {
+    let mut sum = 0;
+    for_each_window(reader, |window| {
+        sum += gear_ratios_in(window, 1).sum::<usize>();
+    })?;
+    Ok(sum.to_string())
 }
 
-#[tracing::instrument]
-pub fn process<'a>(input: &'a str) -> miette::Result<String, AocError> {
-    let (numbers, symbols) = parse(input).unwrap();
-    let valid_parts = validate_gears(&numbers, symbols);
-    Ok(valid_parts
-        .iter()
-        .map(|parts| parts.iter().map(|part| part.value).reduce(|acc, e| acc*e).unwrap())
+#[tracing::instrument(skip(schematic))]
+pub fn solve(schematic: &Schematic) -> miette::Result<String, AocError> {
+    #[cfg(not(feature = "parallel"))]
+    let rows = 0..schematic.height();
+    #[cfg(feature = "parallel")]
+    let rows = (0..schematic.height()).into_par_iter();
+    let sum = tracing::info_span!("sum").in_scope(|| {
+        rows.map(|y| {
+            tracing::info_span!("validate", y)
+                .in_scope(|| gear_ratios_in(schematic, y).sum::<usize>())
+        })
         .sum::<usize>()
-        .to_string())
+    });
+    Ok(sum.to_string())
 }
 
-fn parse<'a>(
-    input: &'a str,
-) -> Result<(BTreeMap<usize, Vec<Number>>, BTreeMap<usize, Vec<usize>>), Box<dyn Error + 'a>> {
-    let mut numbers = BTreeMap::new();
-    let mut symbols = BTreeMap::new();
-    for (y, line) in input.lines().enumerate() {
-        let values = parse_line(line)?.1;
-        for value in values {
-            match value {
-                Value::Number(number) => {
-                    numbers
-                        .entry(y)
-                        .and_modify(|e: &mut Vec<Number>| e.push(number.clone().with(y)))
-                        .or_insert(vec![number.with(y)]);
-                }
-                Value::Symbol(symbol) => {
-                    symbols
-                        .entry(y)
-                        .and_modify(|e: &mut Vec<usize>| e.push(symbol.x))
-                        .or_insert(vec![symbol.x]);
-                }
-                Value::Empty => (),
-            }
-        }
-    }
-
-    Ok((numbers, symbols))
+/// Product of the numbers around each gear of row `y` next to at least two.
+fn gear_ratios_in<'s>(schematic: &'s Schematic, y: usize) -> impl Iterator<Item = usize> + 's {
+    schematic
+        .symbols_in(y)
+        .filter(|symbol| symbol.is_gear())
+        .filter_map(|gear| gear_ratio(schematic, &gear))
 }
 
-fn parse_line(input: &str) -> IResult<Span, Vec<Value>> {
-    let input = Span::new(input.trim());
-    many1(alt((
-        is_a("*").map(Symbol::from).map(Value::Symbol),
-        digit1.map(Number::from).map(Value::Number),
-        take_till1(|c: char| c.is_ascii_digit() || c == '*').map(|_| Value::Empty),
-    )))(input)
+/// Product of the numbers around `gear`, if there are at least two.
+fn gear_ratio(schematic: &Schematic, gear: &Symbol) -> Option<usize> {
+    let parts = schematic.numbers_around(gear).map(|part| part.value());
+    (parts.clone().count() >= 2).then(|| parts.product())
 }
 
-fn validate_gears(
-    numbers: &BTreeMap<usize, Vec<Number>>,
-    symbols: BTreeMap<usize, Vec<usize>>,
-) -> Vec<Vec<&Number>> {
-    let mut all_adj_parts = vec![];
-    for (y, symbols) in symbols {
-        for symobl in symbols {
-            let mut lines_to_check = vec![y.saturating_sub(1), y, y + 1];
-            lines_to_check.dedup();
-            let adj_parts = lines_to_check
-                .into_iter()
-                .filter_map(|y| numbers.get(&y))
-                .flat_map(|y| y)
-                .filter(|number| number.x.contains(&symobl))
+/// Every `*` with the numbers around it and its ratio if it is a gear, summed
+/// as [`process`] does.
+pub fn explain(input: &str) -> miette::Result<Explanation, AocError> {
+    let schematic = parse(input)?;
+    let mut explanation = Explanation::new(["line", "column", "numbers", "ratio"]);
+    let mut sum = 0;
+    for y in 0..schematic.height() {
+        for gear in schematic.symbols_in(y).filter(|symbol| symbol.is_gear()) {
+            let ratio = gear_ratio(&schematic, &gear).unwrap_or_default();
+            sum += ratio;
+            let numbers = schematic
+                .numbers_around(&gear)
+                .map(|number| number.value().to_string())
                 .collect::<Vec<_>>();
-            all_adj_parts.push(adj_parts);
+            explanation.row([
+                (gear.y + 1).to_string(),
+                (gear.x + 1).to_string(),
+                numbers.join(" * "),
+                ratio.to_string(),
+            ]);
         }
     }
-    all_adj_parts.into_iter().filter(|adj_parts| adj_parts.len()>= 2).collect()
+    explanation.answer = sum.to_string();
+    Ok(explanation)
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_core::input::dedent;
+
     use super::*;
 
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "467..114..
-        ...*......
-        ..35..633.
-        ......#...
-        617*......
-        .....+.58.
-        ..592.....
-        ......755.
-        ...$.*....
-        .664.598..";
-        assert_eq!("467835", process(input)?);
+        let input = dedent(
+            "467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..",
+        );
+        assert_eq!("467835", process(&input)?);
+        assert_eq!("467835", process_reader(input.as_bytes())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let input = include_str!("../input2.txt");
+        assert_eq!(process(input)?, explain(input)?.answer);
+        let example = explain("467..114..\n...*......\n..35..633.\n......#...\n617*......")?;
+        assert_eq!(vec!["2", "4", "467 * 35", "16345"], example.rows[0]);
+        assert_eq!(vec!["5", "4", "617", "0"], example.rows[1]);
         Ok(())
     }
 }
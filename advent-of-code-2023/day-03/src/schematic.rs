@@ -0,0 +1,320 @@
+use std::{io::BufRead, ops::RangeInclusive};
+
+use aoc_core::reader::for_each_line;
+
+use crate::custom_error::AocError;
+
+/// A number of the schematic, as a view of its digits in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number<'a> {
+    pub digits: &'a [u8],
+    /// Column of the first digit, counted from the start of its line.
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Number<'_> {
+    pub fn value(&self) -> usize {
+        self.digits
+            .iter()
+            .fold(0, |value, digit| value * 10 + usize::from(digit - b'0'))
+    }
+
+    /// Every column adjacent to or covered by the number.
+    pub fn columns(&self) -> RangeInclusive<usize> {
+        self.x.saturating_sub(1)..=self.x + self.digits.len()
+    }
+
+    pub fn is_adjacent(&self, symbol: &Symbol) -> bool {
+        self.y.abs_diff(symbol.y) <= 1 && self.columns().contains(&symbol.x)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub x: usize,
+    pub y: usize,
+    pub kind: u8,
+}
+
+impl Symbol {
+    pub fn is_gear(&self) -> bool {
+        self.kind == b'*'
+    }
+}
+
+fn is_symbol(byte: &u8) -> bool {
+    !matches!(byte, b'.' | b' ') && !byte.is_ascii_digit()
+}
+
+/// The engine schematic as rows of bytes borrowed from the input. Numbers and
+/// symbols are found by scanning the rows, never collected up front.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Schematic<'a> {
+    rows: Vec<&'a [u8]>,
+}
+
+impl<'a> Schematic<'a> {
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Row `y`, empty outside of the schematic.
+    pub fn row(&self, y: usize) -> &'a [u8] {
+        self.rows.get(y).copied().unwrap_or_default()
+    }
+
+    /// Numbers of row `y`, left to right.
+    pub fn numbers_in(&self, y: usize) -> Numbers<'a> {
+        Numbers {
+            row: self.row(y),
+            x: 0,
+            end: usize::MAX,
+            y,
+        }
+    }
+
+    /// Symbols of row `y`, left to right.
+    pub fn symbols_in(&self, y: usize) -> impl Iterator<Item = Symbol> + 'a {
+        self.row(y)
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| is_symbol(byte))
+            .map(move |(x, &kind)| Symbol { x, y, kind })
+    }
+
+    /// Numbers adjacent to `symbol`, on its row and the rows above and below.
+    pub fn numbers_around(&self, symbol: &Symbol) -> impl Iterator<Item = Number<'a>> + Clone + '_ {
+        let Symbol { x, y, .. } = *symbol;
+        let from = x.saturating_sub(1);
+        (y.saturating_sub(1)..=y + 1).flat_map(move |y| {
+            let row = self.row(y);
+            // Back up to the start of a number running into the first column.
+            let start = row[..from.min(row.len())]
+                .iter()
+                .rposition(|byte| !byte.is_ascii_digit())
+                .map_or(0, |x| x + 1);
+            let start = if row.get(from).is_some_and(u8::is_ascii_digit) {
+                start
+            } else {
+                from
+            };
+            Numbers {
+                row,
+                x: start,
+                end: x + 1,
+                y,
+            }
+        })
+    }
+
+    /// Symbols adjacent to `number`, on its row and the rows above and below.
+    pub fn symbols_around(&self, number: &Number) -> impl Iterator<Item = Symbol> + '_ {
+        let columns = number.columns();
+        (number.y.saturating_sub(1)..=number.y + 1).flat_map(move |y| {
+            let row = self.row(y);
+            let start = (*columns.start()).min(row.len());
+            let end = (*columns.end() + 1).min(row.len());
+            row[start..end]
+                .iter()
+                .enumerate()
+                .filter(|(_, byte)| is_symbol(byte))
+                .map(move |(x, &kind)| Symbol {
+                    x: start + x,
+                    y,
+                    kind,
+                })
+        })
+    }
+}
+
+/// Numbers of a row, left to right from column `x`, up to those starting
+/// after column `end`.
+#[derive(Debug, Clone)]
+pub struct Numbers<'a> {
+    row: &'a [u8],
+    x: usize,
+    end: usize,
+    y: usize,
+}
+
+impl<'a> Iterator for Numbers<'a> {
+    type Item = Number<'a>;
+
+    fn next(&mut self) -> Option<Number<'a>> {
+        let rest = self.row.get(self.x..)?;
+        let start = self.x + rest.iter().position(u8::is_ascii_digit)?;
+        if start > self.end {
+            return None;
+        }
+        let digits = &self.row[start..];
+        let len = digits
+            .iter()
+            .position(|byte| !byte.is_ascii_digit())
+            .unwrap_or(digits.len());
+        self.x = start + len;
+        Some(Number {
+            digits: &digits[..len],
+            x: start,
+            y: self.y,
+        })
+    }
+}
+
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &str) -> Result<Schematic<'_>, AocError> {
+    let mut rows = Vec::with_capacity(input.bytes().filter(|&byte| byte == b'\n').count() + 1);
+    for (y, line) in input.lines().enumerate() {
+        rows.push(parse_row(y, line)?);
+    }
+    Ok(Schematic { rows })
+}
+
+/// Parses the rows of `reader` one line at a time, calling `f` with a
+/// schematic of each row, at `y` 1, between the rows above and below it.
+/// Only those three rows are ever kept.
+pub fn for_each_window(
+    reader: impl BufRead,
+    mut f: impl FnMut(&Schematic<'_>),
+) -> Result<(), AocError> {
+    let mut window: [String; 3] = Default::default();
+    let mut rows = 0;
+    let mut visit = |window: &[String; 3]| {
+        f(&Schematic {
+            rows: window.iter().map(|row| row.as_bytes()).collect(),
+        })
+    };
+    for_each_line(reader, |number, line| {
+        window.rotate_left(1);
+        window[2].clear();
+        window[2].push_str(line);
+        parse_row(number - 1, &window[2])?;
+        if number > 1 {
+            visit(&window);
+        }
+        rows = number;
+        Ok::<_, AocError>(())
+    })?;
+    if rows > 0 {
+        window.rotate_left(1);
+        window[2].clear();
+        visit(&window);
+    }
+    Ok(())
+}
+
+/// Row `y` of the schematic, which may only hold printable characters. The
+/// line is kept whole, spaces being empty like `.`, so that columns are those
+/// of the input.
+fn parse_row(y: usize, line: &str) -> Result<&[u8], AocError> {
+    let row = line.as_bytes();
+    if !row
+        .iter()
+        .all(|&byte| byte == b' ' || byte.is_ascii_graphic())
+    {
+        return Err(AocError::ParseError { line: y + 1 });
+    }
+    Ok(row)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::input::dedent;
+
+    use super::*;
+
+    #[test]
+    fn test_numbers_in() -> miette::Result<()> {
+        let schematic = parse("300.400..500%..")?;
+        assert_eq!(
+            vec![(300, 0..=3), (400, 3..=7), (500, 8..=12)],
+            schematic
+                .numbers_in(0)
+                .map(|number| (number.value(), number.columns()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Symbol {
+                x: 12,
+                y: 0,
+                kind: b'%'
+            }],
+            schematic.symbols_in(0).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_adjacent_symbols() -> miette::Result<()> {
+        let schematic = parse("1*#2")?;
+        assert_eq!(
+            vec![b'*', b'#'],
+            schematic
+                .symbols_in(0)
+                .map(|symbol| symbol.kind)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_numbers_around() -> miette::Result<()> {
+        let schematic = parse("123.4\n...*.\n5..67")?;
+        let gear = schematic.symbols_in(1).next().unwrap();
+        assert_eq!(
+            vec![123, 4, 67],
+            schematic
+                .numbers_around(&gear)
+                .map(|number| number.value())
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_leading_spaces_keep_columns() -> miette::Result<()> {
+        let input = dedent(
+            "
+               12.
+            *....#
+            ......",
+        );
+        let around = |schematic: &Schematic, y: usize| {
+            schematic
+                .symbols_in(y)
+                .map(|symbol| {
+                    let numbers = schematic.numbers_around(&symbol);
+                    (symbol.kind, numbers.map(|number| number.value()).collect())
+                })
+                .collect::<Vec<(u8, Vec<usize>)>>()
+        };
+        let expected = vec![(b'*', vec![]), (b'#', vec![12])];
+        assert_eq!(expected, around(&parse(&input)?, 1));
+        let mut windows = Vec::new();
+        for_each_window(input.as_bytes(), |window| windows.push(around(window, 1)))?;
+        assert_eq!(expected, windows[1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            parse("1..\n1\t.2"),
+            Err(AocError::ParseError { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_for_each_window() -> miette::Result<()> {
+        let mut windows = Vec::new();
+        for_each_window("1..\n.*.\n..2".as_bytes(), |window| {
+            windows.push([
+                window.numbers_in(0).count(),
+                window.symbols_in(1).count(),
+                window.numbers_in(2).count(),
+            ]);
+        })?;
+        assert_eq!(vec![[0, 0, 0], [1, 1, 1], [0, 0, 0]], windows);
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use aoc_core::Part;
+use aoc_wasm::registry;
+use leptos::prelude::*;
+
+use crate::visual;
+
+/// What running a part gave.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub answer: Result<String, String>,
+    pub millis: f64,
+}
+
+/// Solves `part` of `day` for `input`, timing it with the browser's clock.
+pub fn run(day: u8, part: Part, input: &str) -> Outcome {
+    let start = now();
+    let answer = aoc_wasm::run(day, part.number(), input);
+    Outcome {
+        answer,
+        millis: now() - start,
+    }
+}
+
+/// Milliseconds since the page loaded. `std::time::Instant` is not
+/// available to `wasm32-unknown-unknown`.
+fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map_or(0.0, |performance| performance.now())
+}
+
+#[component]
+pub fn App() -> impl IntoView {
+    let (day, set_day) = signal(registry::DAYS.first().map_or(1, |puzzle| puzzle.day()));
+    let (part, set_part) = signal(Part::One);
+    let (input, set_input) = signal(String::new());
+    let (outcome, set_outcome) = signal(None::<Outcome>);
+    // The visualisation follows the input that was last run.
+    let (shown, set_shown) = signal(None::<(u8, String)>);
+
+    let solve = move |_| {
+        let (day, input) = (day.get(), input.get());
+        set_outcome.set(Some(run(day, part.get(), &input)));
+        set_shown.set(Some((day, input)));
+    };
+
+    view! {
+        <h1>"Advent of Code 2023"</h1>
+        <p>
+            <select on:change=move |event| {
+                if let Ok(day) = event_target_value(&event).parse() {
+                    set_day.set(day);
+                }
+            }>
+                {registry::DAYS
+                    .iter()
+                    .map(|puzzle| {
+                        view! {
+                            <option value=puzzle.day().to_string()>
+                                {format!("day {:02}: {}", puzzle.day(), puzzle.name())}
+                            </option>
+                        }
+                    })
+                    .collect_view()}
+            </select>
+            " "
+            <select on:change=move |event| {
+                if let Ok(part) = event_target_value(&event).parse() {
+                    set_part.set(part);
+                }
+            }>
+                {Part::ALL
+                    .map(|part| view! { <option value=part.number().to_string()>{part.to_string()}</option> })
+                    .collect_view()}
+            </select>
+            " "
+            <button on:click=solve>"Run"</button>
+        </p>
+        <textarea
+            placeholder="Paste the puzzle input here"
+            prop:value=input
+            on:input=move |event| set_input.set(event_target_value(&event))
+        ></textarea>
+        {move || {
+            outcome
+                .get()
+                .map(|outcome| match outcome.answer {
+                    Ok(answer) => {
+                        view! {
+                            <p>
+                                <span class="answer">{answer}</span>
+                                {format!(" in {:.3} ms", outcome.millis)}
+                            </p>
+                        }
+                            .into_any()
+                    }
+                    Err(error) => view! { <p class="error">{error}</p> }.into_any(),
+                })
+        }}
+        {move || shown.get().and_then(|(day, input)| visual::view(day, &input))}
+    }
+}
@@ -0,0 +1,11 @@
+pub mod app;
+pub mod visual;
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Mounts the page once the WASM module is loaded.
+#[wasm_bindgen(start)]
+pub fn start() {
+    console_error_panic_hook::set_once();
+    leptos::mount::mount_to_body(app::App);
+}
@@ -0,0 +1,207 @@
+use aoc_core::normalize;
+use day_02::{part1::is_possible, part2::power, Color};
+use day_03::{part1::validate_parts_in, schematic::Schematic};
+use leptos::prelude::*;
+
+/// A game of day 2, as a row of the games table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRow {
+    pub id: u32,
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+    pub possible: bool,
+    pub power: u32,
+}
+
+pub fn game_rows(input: &str) -> Result<Vec<GameRow>, String> {
+    let games = day_02::parse(&normalize(input)).map_err(|error| error.to_string())?;
+    Ok(games
+        .iter()
+        .map(|game| GameRow {
+            id: game.id,
+            red: game.max(Color::Red),
+            green: game.max(Color::Green),
+            blue: game.max(Color::Blue),
+            possible: is_possible(game),
+            power: power(game),
+        })
+        .collect())
+}
+
+/// What a character of the day 3 schematic is to the solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Empty,
+    /// A digit of a number next to a symbol.
+    Part,
+    /// A digit of a number next to no symbol.
+    Number,
+    Symbol,
+    /// A `*` next to at least two numbers.
+    Gear,
+}
+
+impl Kind {
+    fn class(self) -> &'static str {
+        match self {
+            Kind::Empty => "empty",
+            Kind::Part => "part",
+            Kind::Number => "number",
+            Kind::Symbol => "symbol",
+            Kind::Gear => "gear",
+        }
+    }
+}
+
+/// The schematic of day 3 as rows of characters and their [`Kind`].
+pub fn schematic_cells(input: &str) -> Result<Vec<Vec<(char, Kind)>>, String> {
+    let input = normalize(input);
+    let schematic = day_03::schematic::parse(&input).map_err(|error| error.to_string())?;
+    Ok((0..schematic.height())
+        .map(|y| schematic_row(&schematic, y))
+        .collect())
+}
+
+fn schematic_row(schematic: &Schematic, y: usize) -> Vec<(char, Kind)> {
+    let mut row = schematic
+        .row(y)
+        .iter()
+        .map(|&byte| (char::from(byte), Kind::Empty))
+        .collect::<Vec<_>>();
+    let mut mark = |x: usize, byte: u8, kind: Kind| row[x] = (char::from(byte), kind);
+    for number in schematic.numbers_in(y) {
+        for (offset, &digit) in number.digits.iter().enumerate() {
+            mark(number.x + offset, digit, Kind::Number);
+        }
+    }
+    for part in validate_parts_in(schematic, y) {
+        for (offset, &digit) in part.digits.iter().enumerate() {
+            mark(part.x + offset, digit, Kind::Part);
+        }
+    }
+    for symbol in schematic.symbols_in(y) {
+        let gear = symbol.is_gear() && schematic.numbers_around(&symbol).count() >= 2;
+        let kind = if gear { Kind::Gear } else { Kind::Symbol };
+        mark(symbol.x, symbol.kind, kind);
+    }
+    row
+}
+
+/// The visualisation of `day` for `input`, if it has one.
+pub fn view(day: u8, input: &str) -> Option<AnyView> {
+    match day {
+        2 => Some(game_table(input).into_any()),
+        3 => Some(schematic(input).into_any()),
+        _ => None,
+    }
+}
+
+fn game_table(input: &str) -> impl IntoView {
+    let rows = match game_rows(input) {
+        Ok(rows) => rows,
+        Err(error) => return view! { <p class="error">{error}</p> }.into_any(),
+    };
+    view! {
+        <table>
+            <tr>
+                <th>"game"</th>
+                <th>"red"</th>
+                <th>"green"</th>
+                <th>"blue"</th>
+                <th>"possible"</th>
+                <th>"power"</th>
+            </tr>
+            {rows
+                .into_iter()
+                .map(|row| {
+                    view! {
+                        <tr class:impossible=!row.possible>
+                            <td>{row.id}</td>
+                            <td>{row.red}</td>
+                            <td>{row.green}</td>
+                            <td>{row.blue}</td>
+                            <td>{if row.possible { "yes" } else { "no" }}</td>
+                            <td>{row.power}</td>
+                        </tr>
+                    }
+                })
+                .collect_view()}
+        </table>
+    }
+    .into_any()
+}
+
+fn schematic(input: &str) -> impl IntoView {
+    let rows = match schematic_cells(input) {
+        Ok(rows) => rows,
+        Err(error) => return view! { <p class="error">{error}</p> }.into_any(),
+    };
+    view! {
+        <pre class="schematic">
+            {rows
+                .into_iter()
+                .map(|row| {
+                    let cells = row
+                        .into_iter()
+                        .map(|(character, kind)| {
+                            view! { <span class=kind.class()>{character.to_string()}</span> }
+                        })
+                        .collect_view();
+                    view! { {cells} "\n" }
+                })
+                .collect_view()}
+        </pre>
+    }
+    .into_any()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_rows() -> Result<(), String> {
+        let rows = game_rows(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        )?;
+        assert_eq!(
+            vec![
+                GameRow {
+                    id: 1,
+                    red: 4,
+                    green: 2,
+                    blue: 6,
+                    possible: true,
+                    power: 48,
+                },
+                GameRow {
+                    id: 3,
+                    red: 20,
+                    green: 13,
+                    blue: 6,
+                    possible: false,
+                    power: 1560,
+                },
+            ],
+            rows
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_schematic_cells() -> Result<(), String> {
+        let cells = schematic_cells("467..114\n...*....\n..35....")?;
+        let kinds = |y: usize| {
+            cells[y]
+                .iter()
+                .map(|(_, kind)| kind.class().chars().next().unwrap())
+                .collect::<String>()
+        };
+        assert_eq!("pppeennn", kinds(0));
+        assert_eq!("eeegeeee", kinds(1));
+        assert_eq!("eeppeeee", kinds(2));
+        Ok(())
+    }
+}
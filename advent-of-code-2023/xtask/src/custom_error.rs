@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error("{path}: {source}")]
+    #[diagnostic(code(xtask::io_error))]
+    IoError {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("there is no day {0}")]
+    #[diagnostic(code(xtask::bad_day), help("days go from 1 to 25"))]
+    BadDay(u8),
+
+    #[error("{0} already exists")]
+    #[diagnostic(code(xtask::day_exists), help("remove it first to start the day over"))]
+    DayExists(PathBuf),
+
+    #[error("{day} is already registered in {path}")]
+    #[diagnostic(code(xtask::already_registered))]
+    AlreadyRegistered { day: String, path: PathBuf },
+
+    #[error("could not find {what} in {path}")]
+    #[diagnostic(code(xtask::registration))]
+    Registration { what: &'static str, path: PathBuf },
+
+    #[error("unknown template placeholder `{0}`")]
+    #[diagnostic(code(xtask::template))]
+    Template(String),
+
+    #[error("template is missing a `{0}`")]
+    #[diagnostic(code(xtask::template))]
+    UnbalancedTemplate(&'static str),
+
+    #[error("`{0}` is not an optional dependency of the template")]
+    #[diagnostic(
+        code(xtask::unknown_dependency),
+        help("pick from the bool placeholders of `daily-template/cargo-generate.toml`")
+    )]
+    UnknownDependency(String),
+}
@@ -0,0 +1,11 @@
+pub mod custom_error;
+pub mod new;
+pub mod template;
+
+use std::path::Path;
+
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask lives inside the workspace")
+}
@@ -0,0 +1,31 @@
+use clap::{Parser, Subcommand};
+use xtask::{new, workspace_root};
+
+#[derive(Parser, Debug)]
+#[command(name = "xtask", about = "Workspace chores, run with `cargo xtask`")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create `day-XX` from `daily-template` and register it with the runner
+    New {
+        day: u8,
+        /// Optional dependency to start with: glam, nom_locate or petgraph
+        #[arg(long = "with", value_name = "DEPENDENCY")]
+        dependencies: Vec<String>,
+    },
+}
+
+fn main() -> miette::Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::New { day, dependencies } => {
+            let dir = new::new_day(workspace_root(), day, &dependencies)?;
+            println!("created {}", dir.display());
+        }
+    }
+    Ok(())
+}
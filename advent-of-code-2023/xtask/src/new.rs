@@ -0,0 +1,285 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    custom_error::AocError,
+    template::{self, Vars},
+};
+
+/// Template files that describe the template itself rather than a day.
+const SKIPPED: &[&str] = &["cargo-generate.toml", "target"];
+
+/// Longest `DAYS` line rustfmt keeps on one line: its `max_width` of 100
+/// less the `&` and `;` around the array.
+const MAX_WIDTH: usize = 98;
+
+/// Creates `day-XX` in the workspace at `root` from `daily-template`, with
+/// empty inputs and the optional `dependencies`, and registers it with the
+/// `aoc` runner, the `aoc-wasm` build and `answers.txt`.
+///
+/// Every file is checked before anything is written, and an existing day is
+/// never overwritten.
+pub fn new_day(root: &Path, day: u8, dependencies: &[String]) -> Result<PathBuf, AocError> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::BadDay(day));
+    }
+    let name = format!("day-{day:02}");
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(AocError::DayExists(dir));
+    }
+    let vars = template::day_vars(day, dependencies)?;
+
+    let answers = root.join("answers.txt");
+    let mut edits = vec![(answers.clone(), register_answers(&read(&answers)?, day))];
+    // The runner, and the WASM build when there is one.
+    for package in ["aoc", "aoc-wasm"] {
+        let manifest = root.join(package).join("Cargo.toml");
+        if package != "aoc" && !manifest.exists() {
+            continue;
+        }
+        let registry = root.join(package).join("src").join("registry.rs");
+        edits.push((
+            manifest.clone(),
+            register_dependency(&read(&manifest)?, &name, &manifest)?,
+        ));
+        edits.push((
+            registry.clone(),
+            register_puzzle(&read(&registry)?, day, &registry)?,
+        ));
+    }
+
+    copy_template(&root.join("daily-template"), &dir, &vars)?;
+    for part in 1..=2 {
+        write(&dir.join(format!("input{part}.txt")), "")?;
+    }
+    for (path, content) in edits {
+        write(&path, &content)?;
+    }
+    Ok(dir)
+}
+
+fn read(path: &Path) -> Result<String, AocError> {
+    fs::read_to_string(path).map_err(|source| AocError::IoError {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write(path: &Path, content: &str) -> Result<(), AocError> {
+    fs::write(path, content).map_err(|source| AocError::IoError {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn copy_template(from: &Path, to: &Path, vars: &Vars) -> Result<(), AocError> {
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| AocError::IoError { path, source }
+    };
+    fs::create_dir_all(to).map_err(io_error(to))?;
+    for entry in fs::read_dir(from).map_err(io_error(from))? {
+        let entry = entry.map_err(io_error(from))?;
+        let file_name = entry.file_name();
+        if SKIPPED.iter().any(|skipped| file_name == *skipped) {
+            continue;
+        }
+        let (source, target) = (entry.path(), to.join(&file_name));
+        if entry.file_type().map_err(io_error(&source))?.is_dir() {
+            copy_template(&source, &target, vars)?;
+        } else {
+            write(&target, &template::render(&read(&source)?, vars)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Adds `name` to a package's path dependencies, in the same form as the day
+/// before it, and to its `parallel` feature if it has one.
+fn register_dependency(manifest: &str, name: &str, path: &Path) -> Result<String, AocError> {
+    if manifest
+        .lines()
+        .any(|line| line.starts_with(&format!("{name} =")))
+    {
+        return Err(AocError::AlreadyRegistered {
+            day: name.to_string(),
+            path: path.to_path_buf(),
+        });
+    }
+    let mut lines = manifest.lines().map(str::to_string).collect::<Vec<_>>();
+    let last_day = lines
+        .iter()
+        .rposition(|line| line.starts_with("day-"))
+        .ok_or(AocError::Registration {
+            what: "the day dependencies",
+            path: path.to_path_buf(),
+        })?;
+    let previous = lines[last_day]
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let dependency = lines[last_day].replace(&previous, name);
+    lines.insert(last_day + 1, dependency);
+    if let Some(parallel) = lines
+        .iter_mut()
+        .find(|line| line.starts_with("parallel = ["))
+    {
+        if let Some(end) = parallel.rfind(']') {
+            parallel.insert_str(end, &format!(r#", "{name}/parallel""#));
+        }
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the day's solution to `registry::DAYS`, keeping it sorted.
+fn register_puzzle(source: &str, day: u8, path: &Path) -> Result<String, AocError> {
+    const START: &str = "pub static DAYS: &[&dyn Puzzle] = &[";
+    let missing = || AocError::Registration {
+        what: "`DAYS`",
+        path: path.to_path_buf(),
+    };
+    let start = source.find(START).ok_or_else(missing)?;
+    let end = start + source[start..].find("];").ok_or_else(missing)?;
+    let puzzle = format!("&day_{day:02}::Day{day:02}");
+    let mut days = source[start + START.len()..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|day| !day.is_empty())
+        .collect::<Vec<_>>();
+    if days.contains(&puzzle.as_str()) {
+        return Err(AocError::AlreadyRegistered {
+            day: puzzle,
+            path: path.to_path_buf(),
+        });
+    }
+    days.push(&puzzle);
+    days.sort_unstable();
+
+    let one_line = format!("{START}{}];", days.join(", "));
+    let list = if one_line.len() <= MAX_WIDTH {
+        one_line
+    } else {
+        let days = days
+            .iter()
+            .map(|day| format!("    {day},\n"))
+            .collect::<String>();
+        format!("{}\n{days}];", START)
+    };
+    Ok(format!("{}{list}{}", &source[..start], &source[end + 2..]))
+}
+
+/// Leaves commented lines for the day's answers, to fill in once accepted.
+fn register_answers(answers: &str, day: u8) -> String {
+    let mut answers = answers.to_string();
+    if !answers.is_empty() && !answers.ends_with('\n') {
+        answers.push('\n');
+    }
+    for part in 1..=2 {
+        answers.push_str(&format!("# {day} {part} <answer>\n"));
+    }
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[dependencies]
+clap = { workspace = true }
+day-01 = { path = "../day-01" }
+day-02 = { path = "../day-02" }
+
+[features]
+parallel = ["day-01/parallel", "day-02/parallel"]
+"#;
+
+    const REGISTRY: &str = "use aoc_core::Puzzle;
+
+pub static DAYS: &[&dyn Puzzle] = &[&day_01::Day01, &day_02::Day02];
+";
+
+    #[test]
+    fn test_register_dependency() -> miette::Result<()> {
+        let manifest = register_dependency(MANIFEST, "day-03", Path::new("Cargo.toml"))?;
+        assert!(manifest
+            .contains("day-02 = { path = \"../day-02\" }\nday-03 = { path = \"../day-03\" }\n"));
+        assert!(manifest.contains(r#""day-02/parallel", "day-03/parallel"]"#));
+        assert!(matches!(
+            register_dependency(&manifest, "day-03", Path::new("Cargo.toml")),
+            Err(AocError::AlreadyRegistered { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_puzzle() -> miette::Result<()> {
+        let registry = register_puzzle(REGISTRY, 3, Path::new("registry.rs"))?;
+        assert!(registry.contains(
+            "pub static DAYS: &[&dyn Puzzle] = &[&day_01::Day01, &day_02::Day02, &day_03::Day03];\n"
+        ));
+        assert!(register_puzzle(&registry, 4, Path::new("registry.rs"))?
+            .contains("= &[\n    &day_01::Day01,\n"));
+        let registry = register_puzzle(&registry, 5, Path::new("registry.rs"))?;
+        let registry = register_puzzle(&registry, 4, Path::new("registry.rs"))?;
+        assert!(registry.contains("= &[\n    &day_01::Day01,\n"));
+        assert!(registry.contains("    &day_04::Day04,\n    &day_05::Day05,\n];\n"));
+        assert!(matches!(
+            register_puzzle(&registry, 4, Path::new("registry.rs")),
+            Err(AocError::AlreadyRegistered { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_day_refuses_to_overwrite() -> miette::Result<()> {
+        let root = std::env::temp_dir().join(format!("xtask-new-day-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("daily-template/src")).unwrap();
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(
+            root.join("daily-template/src/lib.rs"),
+            "pub struct {{project-name | upper_camel_case}};\n{% if glam %}// glam\n{% endif %}",
+        )
+        .unwrap();
+        fs::write(root.join("daily-template/cargo-generate.toml"), "").unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), MANIFEST).unwrap();
+        fs::write(root.join("aoc/src/registry.rs"), REGISTRY).unwrap();
+        fs::create_dir_all(root.join("aoc-wasm/src")).unwrap();
+        fs::write(
+            root.join("aoc-wasm/Cargo.toml"),
+            "day-02 = { path = \"../day-02\", features = [\"wasm\"] }\n",
+        )
+        .unwrap();
+        fs::write(root.join("aoc-wasm/src/registry.rs"), REGISTRY).unwrap();
+        fs::write(root.join("answers.txt"), "1 1 142").unwrap();
+
+        let dir = new_day(&root, 3, &["glam".to_string()])?;
+        assert_eq!(
+            "pub struct Day03;\n// glam\n",
+            read(&dir.join("src/lib.rs"))?
+        );
+        assert_eq!("", read(&dir.join("input1.txt"))?);
+        for package in ["aoc", "aoc-wasm"] {
+            let registry = read(&root.join(package).join("src/registry.rs"))?;
+            assert!(registry.contains("&day_03::Day03]"));
+        }
+        assert!(read(&root.join("aoc-wasm/Cargo.toml"))?
+            .contains("day-03 = { path = \"../day-03\", features = [\"wasm\"] }\n"));
+        assert!(!dir.join("cargo-generate.toml").exists());
+        assert_eq!(
+            "1 1 142\n# 3 1 <answer>\n# 3 2 <answer>\n",
+            read(&root.join("answers.txt"))?
+        );
+        assert!(matches!(
+            new_day(&root, 3, &[]),
+            Err(AocError::DayExists(_))
+        ));
+        assert!(matches!(new_day(&root, 26, &[]), Err(AocError::BadDay(26))));
+        fs::remove_dir_all(&root).unwrap();
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+
+use crate::custom_error::AocError;
+
+/// Values substituted into `daily-template`, by placeholder name.
+pub type Vars = BTreeMap<&'static str, String>;
+
+/// Optional workspace dependencies a day can start with, each a `bool`
+/// placeholder of `cargo-generate.toml`.
+pub const DEPENDENCIES: &[&str] = &["glam", "nom_locate", "petgraph"];
+
+/// The placeholders of a day, named like cargo-generate's so the template
+/// works with both.
+pub fn day_vars(day: u8, dependencies: &[String]) -> Result<Vars, AocError> {
+    if let Some(unknown) = dependencies
+        .iter()
+        .find(|dependency| !DEPENDENCIES.contains(&dependency.as_str()))
+    {
+        return Err(AocError::UnknownDependency(unknown.clone()));
+    }
+    let name = format!("day-{day:02}");
+    let mut vars = Vars::from([
+        ("crate_name", name.replace('-', "_")),
+        ("day", day.to_string()),
+        ("project-name", name),
+    ]);
+    for dependency in DEPENDENCIES {
+        let enabled = dependencies.iter().any(|enabled| enabled == dependency);
+        vars.insert(dependency, enabled.to_string());
+    }
+    Ok(vars)
+}
+
+/// Renders the `{{ name | filter: arg }}` placeholders and the
+/// `{% if name %}`, `{% else %}` and `{% endif %}` blocks of `text`, the
+/// subset of liquid that cargo-generate templates use.
+pub fn render(text: &str, vars: &Vars) -> Result<String, AocError> {
+    let mut output = String::with_capacity(text.len());
+    // Whether each enclosing `{% if %}` holds; text is only kept if they all do.
+    let mut conditions: Vec<bool> = Vec::new();
+    let mut rest = text;
+    while let Some(start) = [rest.find("{{"), rest.find("{%")]
+        .into_iter()
+        .flatten()
+        .min()
+    {
+        let close = if rest[start..].starts_with("{{") {
+            "}}"
+        } else {
+            "%}"
+        };
+        let end = rest[start..]
+            .find(close)
+            .map(|end| start + end)
+            .ok_or_else(|| AocError::Template(rest[start..].to_string()))?;
+        let inner = rest[start + 2..end].trim();
+        let active = conditions.iter().all(|&holds| holds);
+        if active {
+            output.push_str(&rest[..start]);
+        }
+        if close == "}}" {
+            if active {
+                output.push_str(&expression(inner, vars)?);
+            }
+        } else {
+            tag(inner, vars, &mut conditions)?;
+        }
+        rest = &rest[end + 2..];
+    }
+    if !conditions.is_empty() {
+        return Err(AocError::UnbalancedTemplate("{% endif %}"));
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+fn tag(tag: &str, vars: &Vars, conditions: &mut Vec<bool>) -> Result<(), AocError> {
+    match tag.split_whitespace().collect::<Vec<_>>()[..] {
+        ["if", name] => {
+            let value = vars
+                .get(name)
+                .ok_or_else(|| AocError::Template(name.to_string()))?;
+            conditions.push(value == "true");
+        }
+        ["else"] => {
+            let holds = conditions
+                .last_mut()
+                .ok_or(AocError::UnbalancedTemplate("{% if %}"))?;
+            *holds = !*holds;
+        }
+        ["endif"] => {
+            conditions
+                .pop()
+                .ok_or(AocError::UnbalancedTemplate("{% if %}"))?;
+        }
+        _ => return Err(AocError::Template(tag.to_string())),
+    }
+    Ok(())
+}
+
+// project-name | remove: "day-" | plus: 0
+fn expression(expression: &str, vars: &Vars) -> Result<String, AocError> {
+    let unknown = || AocError::Template(expression.trim().to_string());
+    let mut filters = expression.split('|').map(str::trim);
+    let name = filters.next().unwrap_or_default();
+    let mut value = vars.get(name).ok_or_else(unknown)?.clone();
+    for filter in filters {
+        let (filter, argument) = match filter.split_once(':') {
+            Some((filter, argument)) => (filter.trim(), argument.trim().trim_matches('"')),
+            None => (filter, ""),
+        };
+        value = match filter {
+            "upper_camel_case" => upper_camel_case(&value),
+            "remove" => value.replace(argument, ""),
+            "plus" => {
+                let number = |text: &str| text.parse::<i64>().map_err(|_| unknown());
+                (number(&value)? + number(argument)?).to_string()
+            }
+            _ => return Err(unknown()),
+        };
+    }
+    Ok(value)
+}
+
+fn upper_camel_case(text: &str) -> String {
+    text.split(['-', '_'])
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() -> miette::Result<()> {
+        let vars = day_vars(4, &[])?;
+        assert_eq!(
+            "pub struct Day04; // day_04, day 4, 4",
+            render(
+                r#"pub struct {{project-name | upper_camel_case}}; // {{crate_name}}, day {{day}}, {{ project-name | remove: "day-" | plus: 0 }}"#,
+                &vars
+            )?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_if() -> miette::Result<()> {
+        let template =
+            "nom\n{% if nom_locate %}nom_locate\n{% else %}no spans\n{% endif %}tracing\n";
+        let vars = day_vars(4, &["nom_locate".to_string()])?;
+        assert_eq!("nom\nnom_locate\ntracing\n", render(template, &vars)?);
+        let vars = day_vars(4, &[])?;
+        assert_eq!("nom\nno spans\ntracing\n", render(template, &vars)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_errors() -> miette::Result<()> {
+        let vars = day_vars(4, &[])?;
+        assert!(matches!(
+            render("{{ day_name }}", &vars),
+            Err(AocError::Template(placeholder)) if placeholder == "day_name"
+        ));
+        assert!(matches!(
+            render("{% if glam %}glam", &vars),
+            Err(AocError::UnbalancedTemplate(_))
+        ));
+        assert!(matches!(
+            day_vars(4, &["serde".to_string()]),
+            Err(AocError::UnknownDependency(_))
+        ));
+        Ok(())
+    }
+}